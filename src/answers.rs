@@ -0,0 +1,279 @@
+//! Recorded answers, and checking the solvers against them.
+//!
+//! The answers file has one answer per line, as `<day> <part> <input> <answer>`,
+//! where the input is a file in the crate root and the answer is written the way
+//! the runner prints it. Blank lines and lines starting with `#` are ignored.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::error::{parse_field, Error, ParseError};
+use crate::input::{self, lines, Input};
+use crate::registry::Job;
+
+/// The checked in answers file.
+pub fn default_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("answers.txt")
+}
+
+/// The expected answer to a part for one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Every recorded answer, in file order.
+#[derive(Debug, Default)]
+pub struct Answers {
+    recorded: Vec<Recorded>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = Input::Path(path.to_path_buf()).read()?;
+        return Ok(Answers::parse(&text)?);
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let recorded = lines(text)
+            .filter(|line| !line.text.trim().is_empty() && !line.text.starts_with('#'))
+            .map(|line| line.parse(parse_recorded))
+            .collect::<Result<_, _>>()?;
+        return Ok(Answers { recorded });
+    }
+
+    /// Whether any answer is recorded for the day on the given input.
+    pub fn has_input(&self, day: u8, input: &str) -> bool {
+        self.recorded
+            .iter()
+            .any(|r| r.day == day && r.input == input)
+    }
+
+    /// The answer recorded for a part of a day, given the input's file name.
+    pub fn expected(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        self.recorded
+            .iter()
+            .find(|r| r.day == day && r.part == part && r.input == input)
+            .map(|r| r.answer.as_str())
+    }
+
+    /// The day's default input, followed by every other input with answers
+    /// recorded for the day.
+    pub fn inputs_for(&self, day: u8) -> Vec<String> {
+        let mut inputs = vec![input::default_name(day)];
+        for r in self.recorded.iter().filter(|r| r.day == day) {
+            if !inputs.contains(&r.input) {
+                inputs.push(r.input.clone());
+            }
+        }
+        return inputs;
+    }
+}
+
+fn parse_recorded(s: &str) -> Result<Recorded, ParseError> {
+    let fields: Vec<&str> = s.splitn(4, ' ').collect();
+    if fields.len() < 4 || fields.iter().any(|field| field.is_empty()) {
+        return Err(ParseError::new(
+            1,
+            "expected '<day> <part> <input> <answer>'",
+        ));
+    }
+    Ok(Recorded {
+        day: parse_field(fields[0], 1, "a day")?,
+        part: parse_field(fields[1], fields[0].len() + 2, "a part")?,
+        input: fields[2].to_owned(),
+        answer: fields[3].to_owned(),
+    })
+}
+
+/// How a solver's answer compares to the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// There is no answer recorded for this part and input.
+    Missing {
+        actual: String,
+    },
+    /// The input could not be read or parsed.
+    Error(String),
+    /// The input file is not checked in, and no answers are recorded for it.
+    NoInput,
+}
+
+/// The outcome of one part of a day on one input.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+/// Runs every job on the day's default input and on every other input that has
+/// answers recorded for that day, and compares each answer with the recorded one.
+/// A default input that does not exist is only an error if answers are recorded
+/// for it.
+pub fn verify(answers: &Answers, jobs: &[Job]) -> Vec<Check> {
+    let mut checks = Vec::new();
+    for job in jobs {
+        let day = job.day();
+        for name in answers.inputs_for(day) {
+            let input = Input::in_crate(&name);
+            if !input.exists() && !answers.has_input(day, &name) {
+                checks.extend(job.parts.iter().map(|&part| Check {
+                    day,
+                    part,
+                    input: name.clone(),
+                    outcome: Outcome::NoInput,
+                    elapsed: Duration::ZERO,
+                }));
+                continue;
+            }
+            let result = job.solver.run(&input, &job.parts);
+            let results = match result {
+                Ok(results) => results,
+                Err(err) => {
+                    checks.extend(job.parts.iter().map(|&part| Check {
+                        day,
+                        part,
+                        input: name.clone(),
+                        outcome: Outcome::Error(err.to_string()),
+                        elapsed: Duration::ZERO,
+                    }));
+                    continue;
+                }
+            };
+            for answer in results {
+                let outcome = match answers.expected(day, answer.part, &name) {
+                    Some(expected) if expected == answer.value => Outcome::Pass,
+                    Some(expected) => Outcome::Fail {
+                        expected: expected.to_owned(),
+                        actual: answer.value,
+                    },
+                    None => Outcome::Missing {
+                        actual: answer.value,
+                    },
+                };
+                checks.push(Check {
+                    day,
+                    part: answer.part,
+                    input: name.clone(),
+                    outcome,
+                    elapsed: answer.elapsed,
+                });
+            }
+        }
+    }
+    return checks;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::solution::{Answer, Kind, Solver};
+
+    /// Answers "1" to part 1 and "2" to part 2 of day 99, whatever the input.
+    struct Stub;
+
+    impl Solver for Stub {
+        fn day(&self) -> u8 {
+            99
+        }
+
+        fn parts(&self) -> Vec<u8> {
+            vec![1, 2]
+        }
+
+        fn run(&self, input: &Input, parts: &[u8]) -> Result<Vec<Answer>, Error> {
+            let (_, answers) = self.solve(&input.to_string(), parts)?;
+            return Ok(answers);
+        }
+
+        fn solve(&self, _text: &str, parts: &[u8]) -> Result<(Duration, Vec<Answer>), ParseError> {
+            let answers = parts
+                .iter()
+                .map(|&part| Answer {
+                    part,
+                    value: part.to_string(),
+                    kind: Kind::Integer,
+                    elapsed: Duration::ZERO,
+                })
+                .collect();
+            return Ok((Duration::ZERO, answers));
+        }
+    }
+
+    #[test]
+    fn parse_skips_comments_and_blank_lines() {
+        let answers = Answers::parse("# day 1\n\n1 1 input1.t 7\n  \n1 2 input1.t 5\n").unwrap();
+        assert_eq!(answers.recorded.len(), 2);
+        assert_eq!(answers.expected(1, 1, "input1.t"), Some("7"));
+        assert_eq!(answers.expected(1, 2, "input1.t"), Some("5"));
+        assert_eq!(answers.expected(1, 2, "input2.t"), None);
+    }
+
+    #[test]
+    fn parse_keeps_spaces_in_answers() {
+        let answers = Answers::parse("3 1 input3.t (1337, 2758)").unwrap();
+        assert_eq!(answers.expected(3, 1, "input3.t"), Some("(1337, 2758)"));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_lines() {
+        let err = Answers::parse("1 1 input1.t 7\n1 2 input1.t").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("line 2, column 1: expected '<day> <part> <input> <answer>'"));
+        assert!(Answers::parse("one 1 input1.t 7").is_err());
+        assert!(Answers::parse("1 x input1.t 7").is_err());
+        assert!(Answers::parse("1  input1.t 7").is_err());
+    }
+
+    #[test]
+    fn verify_classifies_outcomes() {
+        let answers = Answers::parse("99 1 stub.t 1\n99 2 stub.t 3\n99 1 other.t 1").unwrap();
+        let jobs = [Job {
+            solver: &Stub,
+            parts: vec![1, 2],
+        }];
+        let outcomes: Vec<(u8, String, Outcome)> = verify(&answers, &jobs)
+            .into_iter()
+            .map(|check| (check.part, check.input, check.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                (1, "input99.t".to_owned(), Outcome::NoInput),
+                (2, "input99.t".to_owned(), Outcome::NoInput),
+                (1, "stub.t".to_owned(), Outcome::Pass),
+                (
+                    2,
+                    "stub.t".to_owned(),
+                    Outcome::Fail {
+                        expected: "3".to_owned(),
+                        actual: "2".to_owned()
+                    }
+                ),
+                (1, "other.t".to_owned(), Outcome::Pass),
+                (
+                    2,
+                    "other.t".to_owned(),
+                    Outcome::Missing {
+                        actual: "2".to_owned()
+                    }
+                ),
+            ]
+        );
+    }
+}
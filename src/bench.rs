@@ -0,0 +1,243 @@
+//! Timing the solvers over repeated runs, and comparing the timings with a
+//! saved baseline to catch regressions.
+//!
+//! A baseline file has one median per line, as `<day> <stage> <nanoseconds>`
+//! where the stage is `parse`, `part1` or `part2`.
+
+use std::{
+    fmt::{Display, Write as _},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::error::{parse_field, Error, ParseError};
+use crate::input::{lines, Input};
+use crate::registry::Job;
+
+/// Where baselines are saved unless told otherwise. Timings only make sense on
+/// the machine that took them, so it lives in the untracked target directory.
+pub fn default_baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/bench-baseline.txt")
+}
+
+/// The part of a solver that is timed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => f.write_str("parse"),
+            Stage::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+fn parse_stage(s: &str, column: usize) -> Result<Stage, ParseError> {
+    match s {
+        "parse" => Ok(Stage::Parse),
+        "part1" => Ok(Stage::Part(1)),
+        "part2" => Ok(Stage::Part(2)),
+        other => Err(ParseError::new(
+            column,
+            format!("expected parse, part1 or part2, found '{}'", other),
+        )),
+    }
+}
+
+/// The timings of one stage of a day over every run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub day: u8,
+    pub stage: Stage,
+    pub min: Duration,
+    pub median: Duration,
+}
+
+impl Timing {
+    fn from_samples(day: u8, stage: Stage, mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Timing {
+            day,
+            stage,
+            min: samples[0],
+            median: samples[samples.len() / 2],
+        }
+    }
+}
+
+/// Reads the job's input once, then parses and solves it `runs` times.
+pub fn bench(job: &Job, input: &Input, runs: usize) -> Result<Vec<Timing>, Error> {
+    let text = input.read()?;
+    let mut parse_samples = Vec::new();
+    let mut part_samples = vec![Vec::new(); job.parts.len()];
+    for _ in 0..runs.max(1) {
+        let (parse_time, answers) = job.solver.solve(&text, &job.parts)?;
+        parse_samples.push(parse_time);
+        for (samples, answer) in part_samples.iter_mut().zip(answers) {
+            samples.push(answer.elapsed);
+        }
+    }
+
+    let mut timings = vec![Timing::from_samples(job.day(), Stage::Parse, parse_samples)];
+    for (&part, samples) in job.parts.iter().zip(part_samples) {
+        timings.push(Timing::from_samples(job.day(), Stage::Part(part), samples));
+    }
+    return Ok(timings);
+}
+
+/// Median timings saved from an earlier bench run.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: Vec<(u8, Stage, Duration)>,
+}
+
+impl Baseline {
+    /// Records the median of every timing, replacing any saved for the same
+    /// stage of the same day.
+    pub fn record(&mut self, timings: &[Timing]) {
+        for timing in timings {
+            self.medians
+                .retain(|(day, stage, _)| (*day, *stage) != (timing.day, timing.stage));
+            self.medians.push((timing.day, timing.stage, timing.median));
+        }
+        self.medians
+            .sort_by_key(|(day, stage, _)| (*day, stage.to_string()));
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = Input::Path(path.to_path_buf()).read()?;
+        return Ok(Baseline::parse(&text)?);
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let medians = lines(text)
+            .filter(|line| !line.text.trim().is_empty())
+            .map(|line| line.parse(parse_median))
+            .collect::<Result<_, _>>()?;
+        return Ok(Baseline { medians });
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut text = String::new();
+        for (day, stage, median) in &self.medians {
+            writeln!(text, "{} {} {}", day, stage, median.as_nanos()).unwrap();
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, text)?;
+        return Ok(());
+    }
+
+    /// The saved median for a stage of a day.
+    pub fn median(&self, day: u8, stage: Stage) -> Option<Duration> {
+        self.medians
+            .iter()
+            .find(|(d, s, _)| *d == day && *s == stage)
+            .map(|(_, _, median)| *median)
+    }
+}
+
+fn parse_median(s: &str) -> Result<(u8, Stage, Duration), ParseError> {
+    let fields: Vec<&str> = s.split(' ').collect();
+    if fields.len() != 3 {
+        return Err(ParseError::new(1, "expected '<day> <stage> <nanoseconds>'"));
+    }
+    let stage_column = fields[0].len() + 2;
+    let nanos_column = stage_column + fields[1].len() + 1;
+    Ok((
+        parse_field(fields[0], 1, "a day")?,
+        parse_stage(fields[1], stage_column)?,
+        Duration::from_nanos(parse_field(
+            fields[2],
+            nanos_column,
+            "a number of nanoseconds",
+        )?),
+    ))
+}
+
+/// How much slower (positive) or faster (negative) `median` is than the
+/// baseline, in percent.
+pub fn change_percent(baseline: Duration, median: Duration) -> f64 {
+    let baseline = baseline.as_secs_f64();
+    if baseline == 0.0 {
+        return 0.0;
+    }
+    return (median.as_secs_f64() - baseline) / baseline * 100.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u8, stage: Stage, nanos: u64) -> Timing {
+        let median = Duration::from_nanos(nanos);
+        return Timing {
+            day,
+            stage,
+            min: median,
+            median,
+        };
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let baseline = Baseline::parse("1 parse 100\n\n1 part1 2500\n3 part2 7\n").unwrap();
+        let path = std::env::temp_dir().join(format!("bench-baseline-{}.txt", std::process::id()));
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path);
+        fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.medians, baseline.medians);
+        assert_eq!(
+            loaded.median(1, Stage::Part(1)),
+            Some(Duration::from_nanos(2500))
+        );
+        assert_eq!(loaded.median(3, Stage::Parse), None);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_lines() {
+        let err = Baseline::parse("1 parse 100\n1 part3 100").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("line 2, column 3: expected parse, part1 or part2, found 'part3'"));
+        assert!(Baseline::parse("1 parse").is_err());
+        assert!(Baseline::parse("1 parse fast").is_err());
+    }
+
+    #[test]
+    fn record_replaces_existing_entries() {
+        let mut baseline = Baseline::parse("2 parse 100\n1 part1 200").unwrap();
+        baseline.record(&[timing(2, Stage::Parse, 50), timing(1, Stage::Part(2), 300)]);
+        assert_eq!(
+            baseline.medians,
+            [
+                (1, Stage::Part(1), Duration::from_nanos(200)),
+                (1, Stage::Part(2), Duration::from_nanos(300)),
+                (2, Stage::Parse, Duration::from_nanos(50)),
+            ]
+        );
+    }
+
+    #[test]
+    fn change_percent_compares_with_baseline() {
+        let secs = Duration::from_secs;
+        assert_eq!(change_percent(secs(2), secs(3)), 50.0);
+        assert_eq!(change_percent(secs(4), secs(3)), -25.0);
+        assert_eq!(change_percent(Duration::ZERO, secs(1)), 0.0);
+    }
+
+    #[test]
+    fn timing_takes_min_and_median() {
+        let ms = Duration::from_millis;
+        let timing = Timing::from_samples(1, Stage::Parse, vec![ms(5), ms(1), ms(9), ms(3), ms(4)]);
+        assert_eq!(timing.min, ms(1));
+        assert_eq!(timing.median, ms(4));
+    }
+}
@@ -0,0 +1,164 @@
+//! Just enough of an arbitrary precision unsigned integer to count things that
+//! outgrow `u64`, such as lanternfish after a few thousand days.
+
+use std::fmt::{Debug, Display, Write};
+
+/// An unsigned integer of any size, as base 2^64 digits, least significant
+/// first and without trailing zeros (so zero has no digits at all).
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// The value, if it fits in a `u64`.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0]),
+            _ => None,
+        }
+    }
+
+    /// The remainder after dividing by `divisor`, which must not be 0.
+    pub fn rem(&self, divisor: u64) -> u64 {
+        return self.clone().div_rem_small(divisor);
+    }
+
+    fn trim(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        return self;
+    }
+
+    pub fn add(&self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (&self.limbs, &other.limbs)
+        } else {
+            (&other.limbs, &self.limbs)
+        };
+        let mut limbs = Vec::with_capacity(long.len() + 1);
+        let mut carry = false;
+        for (idx, &a) in long.iter().enumerate() {
+            let (sum, overflow_b) = a.overflowing_add(*short.get(idx).unwrap_or(&0));
+            let (sum, overflow_carry) = sum.overflowing_add(carry as u64);
+            limbs.push(sum);
+            carry = overflow_b || overflow_carry;
+        }
+        if carry {
+            limbs.push(1);
+        }
+        return BigUint { limbs };
+    }
+
+    pub fn mul(&self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = a as u128 * b as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = product as u64;
+                carry = product >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+        return BigUint { limbs }.trim();
+    }
+
+    /// Divides in place by a small divisor, returning the remainder.
+    fn div_rem_small(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0u128;
+        for limb in self.limbs.iter_mut().rev() {
+            let current = (remainder << 64) | *limb as u128;
+            *limb = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        *self = std::mem::take(self).trim();
+        return remainder as u64;
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        BigUint { limbs: vec![value] }.trim()
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the largest power of ten that fits in a u64
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        const CHUNK_DIGITS: usize = 19;
+
+        if self.is_zero() {
+            return f.write_char('0');
+        }
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(CHUNK));
+        }
+        let mut chunks = chunks.iter().rev();
+        write!(f, "{}", chunks.next().unwrap())?;
+        for chunk in chunks {
+            write!(f, "{:0width$}", chunk, width = CHUNK_DIGITS)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shown in decimal, like the primitive integers, so that answers read the
+/// same whatever their type.
+impl Debug for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_with_carry() {
+        let sum = BigUint::from(u64::MAX).add(&BigUint::from(1));
+        assert_eq!(sum.to_string(), "18446744073709551616");
+        assert_eq!(sum.to_u64(), None);
+    }
+
+    #[test]
+    fn multiplies() {
+        let big = BigUint::from(u64::MAX);
+        assert_eq!(
+            big.mul(&big).to_string(),
+            (u64::MAX as u128 * u64::MAX as u128).to_string()
+        );
+        assert!(big.mul(&BigUint::zero()).is_zero());
+        assert_eq!(
+            big.mul(&big).rem(1000),
+            (u64::MAX as u128 * u64::MAX as u128 % 1000) as u64
+        );
+    }
+
+    #[test]
+    fn formats_in_decimal() {
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(format!("{:?}", BigUint::from(1234)), "1234");
+        // 2^128, which needs a zero padded chunk in the middle
+        let power = BigUint::from(1 << 32).mul(&BigUint::from(1 << 32));
+        let power = power.mul(&power);
+        assert_eq!(power.to_string(), "340282366920938463463374607431768211456");
+    }
+}
@@ -0,0 +1,72 @@
+//! Day 1: Sonar Sweep
+
+use crate::error::{parse_field, ParseError};
+use crate::input::lines;
+use crate::solution::Solution;
+
+/// Counts how often the sonar depth increases, alone or over a sliding window of three.
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Parsed = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        lines(input)
+            .map(|line| line.parse(|text| parse_field(text, 1, "a depth")))
+            .collect()
+    }
+
+    fn part1(x: &Vec<i32>) -> i32 {
+        let mut i1 = x.iter();
+        i1.next();
+        return i1.zip(x.iter()).map(|m| (m.0 > m.1) as i32).sum();
+    }
+
+    fn part2(x: &Vec<i32>) -> i32 {
+        // sum each window of three depths
+        let sums: Vec<i32> = x.windows(3).map(|w| w.iter().sum()).collect();
+
+        let mut i1 = sums.iter();
+        i1.next();
+        return i1.zip(sums.iter()).map(|m| (m.0 > m.1) as i32).sum();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+    #[test]
+    fn part1_example() {
+        let depths = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part1(&depths), 7);
+    }
+
+    #[test]
+    fn part2_example() {
+        let depths = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part2(&depths), 5);
+    }
+
+    #[test]
+    fn short_reports_have_no_increases() {
+        let depths = Day1::parse("5").unwrap();
+        assert_eq!(Day1::part1(&depths), 0);
+        assert_eq!(Day1::part2(&depths), 0);
+    }
+}
@@ -1,23 +1,12 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    fs::File,
-    io::{BufRead, BufReader, Lines, Result},
-    path::Path,
-};
-
-fn read_lines<P>(filename: P) -> Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
-}
+//! Day 10: Syntax Scoring
+
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::{NoAnswer, Solution};
+use crate::trace;
 
 fn is_open_char(c: char) -> bool {
-    match c {
-        '{' | '[' | '(' | '<' => true,
-        _ => false,
-    }
+    matches!(c, '{' | '[' | '(' | '<')
 }
 
 fn matching_char(c: char) -> char {
@@ -34,83 +23,258 @@ fn matching_char(c: char) -> char {
     }
 }
 
-fn is_corrupt(line: &str) -> Option<char> {
-    let mut char_stack = Vec::<char>::new();
-    for c in line.chars() {
+/// What a line of chunks turns out to be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// Every chunk is closed.
+    Valid,
+    /// A chunk is closed with the wrong character, at `column` (counting
+    /// from 1).
+    Corrupted {
+        column: usize,
+        expected: char,
+        found: char,
+    },
+    /// The line ends with chunks still open, which `completion` closes.
+    Incomplete { completion: String },
+    /// A closing character at `column` with no chunk open to close.
+    UnbalancedClose { column: usize, found: char },
+}
+
+/// Checks a line in a single pass, stopping at the first character that
+/// does not fit.
+pub fn check(line: &str) -> Check {
+    let mut open = Vec::new();
+    for (idx, c) in line.chars().enumerate() {
         if is_open_char(c) {
-            char_stack.push(c);
-        } else {
-            if matching_char(c) == char_stack.pop().unwrap() {
-                continue;
-            } else {
-                return Some(c);
+            open.push(c);
+            continue;
+        }
+        let column = idx + 1;
+        match open.pop() {
+            Some(opening) if matching_char(opening) == c => {}
+            Some(opening) => {
+                return Check::Corrupted {
+                    column,
+                    expected: matching_char(opening),
+                    found: c,
+                }
             }
+            None => return Check::UnbalancedClose { column, found: c },
         }
     }
-    return None;
+    if open.is_empty() {
+        return Check::Valid;
+    }
+    // the chunks still open need closing, innermost first
+    return Check::Incomplete {
+        completion: open.iter().rev().map(|&c| matching_char(c)).collect(),
+    };
 }
 
-fn completion(line: &str) -> Vec<char> {
-    let mut char_stack = Vec::<char>::new();
-    for c in line.chars() {
-        if is_open_char(c) {
-            char_stack.push(c);
-        } else {
-            if matching_char(c) == char_stack.pop().unwrap() {
-                continue;
-            }
+impl Check {
+    /// What is wrong with `line`, which this is the check of, as an error
+    /// pointing at the offending column, or `None` if it is valid.
+    pub fn error(&self, line: &str) -> Option<ParseError> {
+        match self {
+            Check::Valid => None,
+            Check::Corrupted {
+                column,
+                expected,
+                found,
+            } => Some(ParseError::new(
+                *column,
+                format!("expected '{}', found '{}'", expected, found),
+            )),
+            Check::Incomplete { completion } => Some(ParseError::new(
+                line.chars().count() + 1,
+                format!("incomplete, expected '{}'", completion),
+            )),
+            Check::UnbalancedClose { column, found } => Some(ParseError::new(
+                *column,
+                format!("found '{}' with no chunk to close", found),
+            )),
         }
     }
-    // stack now contains openings that need to be closed
-    let completion_stack = Vec::<char>::new();
-    return char_stack.iter().rev().map(|&c| matching_char(c)).collect();
+
+    /// The error in line `number` (counting from 1), shown under it with a
+    /// caret at the offending column.
+    pub fn render(&self, number: usize, line: &str) -> Option<String> {
+        self.error(line)
+            .map(|err| err.at_line(number, line).to_string())
+    }
 }
 
-fn completion_score(completion_stack: Vec<char>, score_map: &HashMap<char, usize>) -> usize {
-    let mut score: usize = 0;
-    for c in completion_stack {
-        score = score * 5;
-        score += score_map.get(&c).unwrap();
+fn corruption_score(c: char) -> i32 {
+    match c {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        _ => 0,
     }
+}
 
-    return score;
+/// The score of a completion, or `None` if it does not fit in a `usize`.
+fn completion_score(completion: &str) -> Option<usize> {
+    let mut score: usize = 0;
+    for c in completion.chars() {
+        let points = match c {
+            ')' => 1,
+            ']' => 2,
+            '}' => 3,
+            '>' => 4,
+            _ => 0,
+        };
+        score = score.checked_mul(5)?.checked_add(points)?;
+    }
+    return Some(score);
 }
 
-pub(crate) fn day10part1() -> i32 {
-    let mut lines = read_lines("input10.t").unwrap();
-    // let corrupt_chars = Vec::<char>::new();
-    let mut score_map = HashMap::<char, i32>::new();
-    score_map.insert(')', 3);
-    score_map.insert(']', 57);
-    score_map.insert('}', 1197);
-    score_map.insert('>', 25137);
-
-    let mut score = 0;
-    for line in lines {
-        let l = line.unwrap();
-        println!("going to check line {}", &l);
-        if let Some(err) = is_corrupt(&l) {
-            score += score_map.get(&err).unwrap();
+/// Scores corrupted and incomplete lines of navigation subsystem syntax.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Parsed = Vec<String>;
+    type Part1 = i32;
+    type Part2 = Result<usize, NoAnswer>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut chunks = Vec::new();
+        for line in lines(input) {
+            if let Some(column) = line.text.find(|c| matching_char(c) == ' ') {
+                let c = line.text[column..].chars().next().unwrap();
+                let reason = format!("expected a bracket, found '{}'", c);
+                return Err(line.error(ParseError::new(column + 1, reason)));
+            }
+            chunks.push(line.text.to_owned());
+        }
+        Ok(chunks)
+    }
+
+    fn part1(lines: &Vec<String>) -> i32 {
+        let mut score = 0;
+        for (idx, l) in lines.iter().enumerate() {
+            let check = check(l);
+            if let Some(message) = check.render(idx + 1, l) {
+                trace!("{}", message);
+            }
+            if let Check::Corrupted { found, .. } = check {
+                score += corruption_score(found);
+            }
+        }
+        return score;
+    }
+
+    fn part2(lines: &Vec<String>) -> Result<usize, NoAnswer> {
+        let mut scores: Vec<usize> = lines
+            .iter()
+            .filter_map(|l| match check(l) {
+                Check::Incomplete { completion } => Some(completion_score(&completion)),
+                _ => None,
+            })
+            .collect::<Option<_>>()
+            .ok_or(NoAnswer("a completion score is too large"))?;
+        if scores.is_empty() {
+            return Err(NoAnswer("no line is incomplete"));
         }
+        scores.sort();
+        return Ok(scores[scores.len() / 2]);
     }
-    return score;
 }
 
-pub(crate) fn day10part2() -> usize {
-    let mut lines = read_lines("input10.t").unwrap();
-    // let corrupt_chars = Vec::<char>::new();
-    let mut score_map = HashMap::<char, usize>::new();
-    score_map.insert(')', 1);
-    score_map.insert(']', 2);
-    score_map.insert('}', 3);
-    score_map.insert('>', 4);
-
-    let incomplete_lines = lines
-        .map(|l| l.unwrap())
-        .filter(|l| is_corrupt(&l).is_none());
-    let mut scores: Vec<usize> = incomplete_lines
-        .map(|l| completion_score(completion(&l), &score_map))
-        .collect();
-    scores.sort();
-    return scores[scores.len() / 2];
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+    #[test]
+    fn part1_example() {
+        let chunks = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part1(&chunks), 26397);
+    }
+
+    #[test]
+    fn part2_example() {
+        let chunks = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part2(&chunks), Ok(288957));
+    }
+
+    #[test]
+    fn no_incomplete_lines() {
+        let chunks = Day10::parse("()\n(]").unwrap();
+        assert_eq!(
+            Day10::part2(&chunks),
+            Err(NoAnswer("no line is incomplete"))
+        );
+    }
+
+    #[test]
+    fn completion_score_overflow() {
+        let chunks = Day10::parse(&"(".repeat(30)).unwrap();
+        assert_eq!(
+            Day10::part2(&chunks),
+            Err(NoAnswer("a completion score is too large"))
+        );
+    }
+
+    #[test]
+    fn classifies_lines() {
+        assert_eq!(check("[<>({}){}[([])<>]]"), Check::Valid);
+        assert_eq!(
+            check("{([(<{}[<>[]}>{[]{[(<()>"),
+            Check::Corrupted {
+                column: 13,
+                expected: ']',
+                found: '}'
+            }
+        );
+        assert_eq!(
+            check("[({(<(())[]>[[{[]{<()<>>"),
+            Check::Incomplete {
+                completion: "}}]])})]".to_string()
+            }
+        );
+        assert_eq!(
+            check(")()"),
+            Check::UnbalancedClose {
+                column: 1,
+                found: ')'
+            }
+        );
+        assert_eq!(
+            check("()]"),
+            Check::UnbalancedClose {
+                column: 3,
+                found: ']'
+            }
+        );
+    }
+
+    #[test]
+    fn renders_errors() {
+        let line = "{([(<{}[<>[]}>{[]{[(<()>";
+        assert_eq!(
+            check(line).render(3, line).unwrap(),
+            "line 3, column 13: expected ']', found '}'\n    \
+             {([(<{}[<>[]}>{[]{[(<()>\n                \
+             ^"
+        );
+        let message = check("((").render(1, "((").unwrap();
+        assert!(message.starts_with("line 1, column 3: incomplete, expected '))'"));
+        assert!(message.ends_with("\n      ^"));
+        assert_eq!(check("()").render(1, "()"), None);
+    }
 }
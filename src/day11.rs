@@ -1,26 +1,15 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fmt::Display,
-    fs::File,
-    io::{BufRead, BufReader, Lines, Result},
-    path::Path,
-};
-
-fn parse_numbers(s: &str) -> Vec<i32> {
-    return s.chars().map(|n| n.to_digit(10).unwrap() as i32).collect();
-}
+//! Day 11: Dumbo Octopus
 
-fn read_lines<P>(filename: P) -> Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
-}
+use std::{collections::VecDeque, fmt::Display};
+
+use crate::error::ParseError;
+use crate::grid::{Grid, Point};
+use crate::solution::Solution;
+use crate::trace;
 
 const FLASH_LEVEL: i32 = 9;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Octopus {
     energy_level: i32,
     num_flashes: usize,
@@ -51,141 +40,124 @@ impl Octopus {
     }
 }
 
-#[derive(Debug)]
-struct OctopusGarden {
-    octopuses: Vec<Octopus>,
+/// The grid of octopuses and their energy levels.
+#[derive(Debug, Clone)]
+pub struct OctopusGarden {
+    octopuses: Grid<Octopus>,
 }
 
 impl Display for OctopusGarden {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let size = (self.octopuses.len() as f32).sqrt() as usize;
-        for (idx, oct) in self.octopuses.iter().enumerate() {
-            f.write_fmt(format_args!("{}", oct.energy_level))?;
-            if idx % size == size - 1 {
-                f.write_str("\n")?;
+        for row in self.octopuses.rows() {
+            for oct in row {
+                f.write_fmt(format_args!("{}", oct.energy_level))?;
             }
+            f.write_str("\n")?;
         }
         Ok(())
     }
 }
 
 impl OctopusGarden {
-    fn step(&mut self) {
+    /// Advances one step, including every flash it sets off.
+    pub fn step(&mut self) {
         // step each of the octopuses, and then flash neighbors as needed
         // let mut flashed: HashSet<usize> = self.octopuses.iter_mut().enumerate().filter(|(idx, oct)| oct.step()).map() collect();
-        let mut flashed: VecDeque<usize> = VecDeque::new();
-        for (idx, oct) in self.octopuses.iter_mut().enumerate() {
-            if oct.step() {
-                flashed.push_back(idx);
+        let mut flashed: VecDeque<Point> = VecDeque::new();
+        let points: Vec<_> = self.octopuses.points().collect();
+        for point in points {
+            if self.octopuses[point].step() {
+                flashed.push_back(point);
             }
         }
 
-        while flashed.len() > 0 {
+        while !flashed.is_empty() {
             // expand via BFS from each of the nodes
-            let oct_idx = flashed.pop_front().unwrap();
-            for n in self.get_neighbors(oct_idx) {
+            let oct_point = flashed.pop_front().unwrap();
+            let neighbors: Vec<_> = self.octopuses.all_neighbors(oct_point).collect();
+            for n in neighbors {
                 if self.octopuses[n].increase_energy() {
                     flashed.push_back(n);
                 }
             }
         }
     }
+}
 
-    fn get_neighbors(&self, idx: usize) -> Vec<usize> {
-        let mut res = Vec::new();
-        let size = (self.octopuses.len() as f32).sqrt() as usize;
-        let is_left_most = idx % size == 0;
-        let is_right_most = idx % size == size - 1;
-        let is_top = idx < size;
-        let is_bottom = idx >= size * (size - 1);
-
-        // not first row
-        if !is_top {
-            res.push(idx - size);
-            // add diagonals
-            if !is_left_most {
-                res.push((idx - size) - 1);
-            }
+/// Steps the flashing octopus garden.
+pub struct Day11;
 
-            if !is_right_most {
-                res.push((idx - size) + 1);
-            }
-        }
+impl Solution for Day11 {
+    const DAY: u8 = 11;
 
-        // not last row
-        if !is_bottom {
-            res.push(idx + size);
-            // add diagonals
-            if !is_left_most {
-                res.push((idx + size) - 1);
-            }
+    type Parsed = OctopusGarden;
+    type Part1 = usize;
+    type Part2 = usize;
 
-            if !is_right_most {
-                res.push((idx + size) + 1);
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let energy_levels = Grid::parse_digits(input)?;
+        let octopuses = energy_levels.map(|&energy_level| Octopus {
+            energy_level: energy_level as i32,
+            flashed_this_step: false,
+            num_flashes: 0,
+        });
+        Ok(OctopusGarden { octopuses })
+    }
 
-        // not first column
-        if !is_left_most {
-            res.push(idx - 1);
+    fn part1(garden: &OctopusGarden) -> usize {
+        let mut garden = garden.clone();
+        trace!("{}", garden);
+        for _ in 0..100 {
+            garden.step();
+            trace!("{}", garden);
         }
 
-        // not last column
-        if !is_right_most {
-            res.push(idx + 1);
-        }
+        let res = garden.octopuses.values().map(|oct| oct.num_flashes).sum();
 
         return res;
     }
-}
 
-pub(crate) fn day11part1() -> usize {
-    let mut lines = read_lines("input11.t").unwrap();
-    let octopuses = lines
-        .map(|l| l.unwrap())
-        .map(|l| parse_numbers(&l))
-        .flat_map(|f| f.into_iter())
-        .map(|energy_level| Octopus {
-            energy_level,
-            flashed_this_step: false,
-            num_flashes: 0,
-        })
-        .collect();
-    let mut garden = OctopusGarden { octopuses };
-    println!("{}", garden);
-    for _ in 0..100 {
-        garden.step();
-        println!("{}", garden);
-    }
-
-    let res = garden.octopuses.iter().map(|oct| oct.num_flashes).sum();
+    fn part2(garden: &OctopusGarden) -> usize {
+        let mut garden = garden.clone();
+        trace!("{}", garden);
+        let mut count = 0;
+        loop {
+            garden.step();
+            count += 1;
+            trace!("{}\n{}", count, garden);
+            if garden.octopuses.values().all(|o| o.flashed_this_step) {
+                break;
+            }
+        }
 
-    return res;
+        return count;
+    }
 }
 
-pub(crate) fn day11part2() -> usize {
-    let mut lines = read_lines("input11.t").unwrap();
-    let octopuses = lines
-        .map(|l| l.unwrap())
-        .map(|l| parse_numbers(&l))
-        .flat_map(|f| f.into_iter())
-        .map(|energy_level| Octopus {
-            energy_level,
-            flashed_this_step: false,
-            num_flashes: 0,
-        })
-        .collect();
-    let mut garden = OctopusGarden { octopuses };
-    println!("{}", garden);
-    let mut count = 0;
-    loop {
-        garden.step();
-        count += 1;
-        println!("{}\n{}", count, garden);
-        if garden.octopuses.iter().all(|o| o.flashed_this_step) {
-            break;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    #[test]
+    fn part1_example() {
+        let garden = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part1(&garden), 1656);
     }
 
-    return count;
+    #[test]
+    fn part2_example() {
+        let garden = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part2(&garden), 195);
+    }
 }
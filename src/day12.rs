@@ -1,164 +1,653 @@
+//! Day 12: Passage Pathing
+
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fmt::Display,
-    fs::File,
-    io::{BufRead, BufReader, Lines, Result},
-    path::Path,
-    thread::current,
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Display, Write},
+    hash::Hash,
 };
 
-fn parse_line(s: &str) -> (String, String) {
-    let mut pair = s.split('-').collect::<Vec<&str>>();
-    (pair[0].to_string(), pair[1].to_string())
+use crate::debug;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
+
+fn parse_line(s: &str) -> Result<(String, String), ParseError> {
+    let (a, b) = s
+        .split_once('-')
+        .ok_or_else(|| ParseError::new(s.len() + 1, "expected two caves joined by '-'"))?;
+    if a.is_empty() {
+        return Err(ParseError::new(1, "expected a cave name before '-'"));
+    }
+    if b.is_empty() || b.contains('-') {
+        return Err(ParseError::new(
+            a.len() + 2,
+            "expected a single cave name after '-'",
+        ));
+    }
+    Ok((a.to_string(), b.to_string()))
 }
 
-fn read_lines<P>(filename: P) -> Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
+/// A cave, by its index in the graph.
+pub type CaveId = usize;
+
+/// A set of caves, with bit `id` set for every cave in it. It grows to fit
+/// the caves put in it, so any number of caves fits.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CaveSet {
+    // never longer than it takes to hold the highest cave, so that equal
+    // sets are equal words
+    words: Vec<u64>,
 }
 
+impl CaveSet {
+    pub fn contains(&self, cave: CaveId) -> bool {
+        self.words
+            .get(cave / 64)
+            .is_some_and(|word| word & (1 << (cave % 64)) != 0)
+    }
+
+    pub fn insert(&mut self, cave: CaveId) {
+        if self.words.len() <= cave / 64 {
+            self.words.resize(cave / 64 + 1, 0);
+        }
+        self.words[cave / 64] |= 1 << (cave % 64);
+    }
+
+    /// This set with `cave` added.
+    pub fn with(&self, cave: CaveId) -> CaveSet {
+        let mut set = self.clone();
+        set.insert(cave);
+        return set;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cave {
+    pub name: String,
+    /// Big caves are named in upper case, and can be visited any number of
+    /// times.
+    pub big: bool,
+}
+
+/// The cave system: every cave and the caves reachable from it. Caves are
+/// referred to by their ID, and their names are only kept for display.
 #[derive(Debug)]
-struct Graph {
-    nodes: HashSet<String>,
-    edges: HashMap<String, Vec<String>>,
+pub struct Graph {
+    caves: Vec<Cave>,
+    /// The caves reachable from each cave. Nothing leads back to the start,
+    /// and nothing leads on from the end.
+    edges: Vec<Vec<CaveId>>,
+    pub start: CaveId,
+    pub end: CaveId,
 }
 
 impl Graph {
-    fn from_lines(lines: Lines<BufReader<File>>) -> Self {
-        let mut graph = Graph {
-            nodes: HashSet::new(),
-            edges: HashMap::new(),
-        };
-        for line in lines {
-            let (a, b) = parse_line(&line.unwrap());
-            graph.nodes.insert(a.clone());
-            graph.nodes.insert(b.clone());
-            let edges_a = graph.edges.entry(a.clone()).or_insert(vec![]);
-            if b != "start" {
-                edges_a.push(b.clone());
+    fn from_lines(input: &str) -> Result<Self, ParseError> {
+        let mut ids: HashMap<String, CaveId> = HashMap::new();
+        let mut caves = Vec::new();
+        let mut edges: Vec<Vec<CaveId>> = Vec::new();
+        for line in lines(input) {
+            let (a, b) = line.parse(parse_line)?;
+            let mut intern = |name: String| {
+                if let Some(&id) = ids.get(&name) {
+                    return id;
+                }
+                ids.insert(name.clone(), caves.len());
+                caves.push(Cave {
+                    big: name.to_uppercase() == name,
+                    name,
+                });
+                edges.push(Vec::new());
+                caves.len() - 1
+            };
+            let (a, b) = (intern(a), intern(b));
+            // a path could go back and forth between them forever
+            if caves[a].big && caves[b].big {
+                let reason = "two big caves can't be joined, as there would be endless paths";
+                return Err(line.error(ParseError::new(1, reason)));
             }
-            let edges_b = graph.edges.entry(b).or_insert(vec![]);
-            if a != "start" {
-                edges_b.push(a);
+            edges[a].push(b);
+            edges[b].push(a);
+        }
+
+        let marker = |name: &str| {
+            ids.get(name).copied().ok_or_else(|| {
+                let reason = format!("the cave system has no '{}' cave", name);
+                ParseError::new(1, reason)
+            })
+        };
+        let (start, end) = (marker("start")?, marker("end")?);
+        for next in edges.iter_mut() {
+            next.retain(|&cave| cave != start);
+        }
+        edges[end].clear();
+
+        return Ok(Graph {
+            caves,
+            edges,
+            start,
+            end,
+        });
+    }
+
+    pub fn cave(&self, id: CaveId) -> &Cave {
+        &self.caves[id]
+    }
+
+    /// The ID of the cave with this name, if there is one.
+    pub fn id(&self, name: &str) -> Option<CaveId> {
+        self.caves.iter().position(|cave| cave.name == name)
+    }
+
+    /// The names of the caves along a path.
+    pub fn names(&self, path: &[CaveId]) -> Vec<&str> {
+        path.iter()
+            .map(|&id| self.caves[id].name.as_str())
+            .collect()
+    }
+
+    /// Every passage between two caves, with the lower ID first, in order.
+    pub fn passages(&self) -> BTreeSet<Passage> {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(|(a, next)| next.iter().map(move |&b| passage(a, b)))
+            .collect()
+    }
+
+    /// How many of `paths` go through each passage, counting a passage once
+    /// for every time a path goes through it.
+    pub fn passage_usage(paths: &[Vec<CaveId>]) -> HashMap<Passage, usize> {
+        let mut usage = HashMap::new();
+        for path in paths {
+            for step in path.windows(2) {
+                *usage.entry(passage(step[0], step[1])).or_insert(0) += 1;
             }
         }
+        return usage;
+    }
 
-        // remove edges starting at 'end' node
-        graph.edges.insert("end".into(), Vec::new());
+    /// The cave system in Graphviz's DOT language, with `overlay` drawn on
+    /// top of it, to be rendered with `dot -Tsvg`.
+    pub fn dot<'a>(&'a self, overlay: Option<Overlay<'a>>) -> Dot<'a> {
+        Dot {
+            graph: self,
+            overlay,
+        }
+    }
 
-        return graph;
+    /// Every path from the start to the end that `policy` allows.
+    pub fn all_paths<P: VisitPolicy>(&self, policy: &P) -> Vec<Vec<CaveId>> {
+        let mut paths = Vec::new();
+        if let Some(state) = policy.enter(self, &policy.start(self), self.start) {
+            self.extend_paths(policy, &mut vec![self.start], &state, &mut paths);
+        }
+        return paths;
     }
 
-    // fn backtrack(&self, mut path: Vec<String>) -> (Vec<String>, String) {
-    //     // 1. remove the latest item in the path
-    //     let curr_last = path.pop().unwrap();
+    fn extend_paths<P: VisitPolicy>(
+        &self,
+        policy: &P,
+        path: &mut Vec<CaveId>,
+        state: &P::State,
+        paths: &mut Vec<Vec<CaveId>>,
+    ) {
+        let current = *path.last().unwrap();
+        if current == self.end {
+            paths.push(path.clone());
+            return;
+        }
+        for &next in &self.edges[current] {
+            if let Some(next_state) = policy.enter(self, state, next) {
+                path.push(next);
+                self.extend_paths(policy, path, &next_state, paths);
+                path.pop();
+            }
+        }
+    }
 
-    //     // take a new sibling for the last element
-    //     let prev_last = path.last().unwrap();
-    //     let possible_neighbors = &self.edges[prev_last];
-    //     let new_index = possible_neighbors
-    //         .iter()
-    //         .position(|x| x == &curr_last)
-    //         .unwrap()
-    //         + 1;
-    //     if new_index == possible_neighbors.len() {
-    //         // this was the last sibling, remove it
-    //     }
-    // }
+    /// How many paths from the start to the end `policy` allows, without
+    /// listing them. Paths are counted by a depth first search that remembers
+    /// how many ways there are to finish from each cave in each state of the
+    /// policy, as the state sums up everything about the path so far that
+    /// matters for where it can go next.
+    pub fn count_paths<P: VisitPolicy>(&self, policy: &P) -> usize {
+        let mut memo = HashMap::new();
+        return match policy.enter(self, &policy.start(self), self.start) {
+            Some(state) => self.count_from(policy, self.start, state, &mut memo),
+            None => 0,
+        };
+    }
 
-    fn all_paths(
+    fn count_from<P: VisitPolicy>(
         &self,
-        start: String,
-        end: String,
-        validation: fn(&String, &Vec<String>) -> bool,
-    ) -> HashSet<Vec<String>> {
-        let mut visited = Vec::<String>::new();
-        visited.push(start);
-        let mut paths = HashSet::<Vec<String>>::new();
-        let mut next_index = 0;
-
-        // assumes there are no loops - so just add to the path if it's possible
-        loop {
-            // try to extend the path, find the next sibling after the last one used
-            let current = visited.last().unwrap().to_owned();
-            let possible_extensions = self.edges.get(&current).unwrap();
-            if possible_extensions.len() > next_index {
-                // check if valid:
-                let ext = &possible_extensions[next_index];
-                if validation(ext, &visited) {
-                    //if &ext.to_uppercase() == ext || !visited.contains(ext) {
-                    visited.push(ext.to_string());
-                    next_index = 0;
-                } else {
-                    next_index += 1;
-                }
+        policy: &P,
+        cave: CaveId,
+        state: P::State,
+        memo: &mut HashMap<(CaveId, P::State), usize>,
+    ) -> usize {
+        if cave == self.end {
+            return 1;
+        }
+        if let Some(&count) = memo.get(&(cave, state.clone())) {
+            return count;
+        }
+        let mut count = 0;
+        for &next in &self.edges[cave] {
+            if let Some(next_state) = policy.enter(self, &state, next) {
+                count += self.count_from(policy, next, next_state, memo);
+            }
+        }
+        memo.insert((cave, state), count);
+        return count;
+    }
+}
+
+/// A passage between two caves, which can be taken both ways, as the IDs of
+/// the caves with the lower one first.
+pub type Passage = (CaveId, CaveId);
+
+fn passage(a: CaveId, b: CaveId) -> Passage {
+    (a.min(b), a.max(b))
+}
+
+/// What to highlight on a drawing of the cave system.
+#[derive(Debug, Clone, Copy)]
+pub enum Overlay<'a> {
+    /// A single path, with its caves and passages drawn in red.
+    Path(&'a [CaveId]),
+    /// How often each passage is used, as from [`Graph::passage_usage`],
+    /// with busier passages drawn thicker.
+    Usage(&'a HashMap<Passage, usize>),
+}
+
+/// A cave system in the DOT language, as written by its `Display`
+/// implementation. Big caves are boxes and small caves ellipses, and the
+/// start and end have a double outline.
+pub struct Dot<'a> {
+    graph: &'a Graph,
+    overlay: Option<Overlay<'a>>,
+}
+
+/// A DOT ID for a cave name, quoted since cave names can be anything.
+fn quoted(name: &str) -> String {
+    let mut id = String::from('"');
+    for c in name.chars() {
+        if c == '"' || c == '\\' {
+            id.push('\\');
+        }
+        id.push(c);
+    }
+    id.push('"');
+    return id;
+}
+
+impl Display for Dot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const HIGHLIGHT: &str = "color=red, fontcolor=red";
+        // how much thicker the busiest passage is drawn than an unused one
+        const MAX_EXTRA_WIDTH: f64 = 4.0;
+
+        let graph = self.graph;
+        let (on_path, path_passages) = match self.overlay {
+            Some(Overlay::Path(path)) => (
+                path.iter().copied().collect(),
+                path.windows(2)
+                    .map(|step| passage(step[0], step[1]))
+                    .collect(),
+            ),
+            _ => (BTreeSet::new(), BTreeSet::new()),
+        };
+
+        writeln!(f, "graph caves {{")?;
+        for (id, cave) in graph.caves.iter().enumerate() {
+            let mut attributes = vec![if cave.big {
+                "shape=box"
             } else {
-                // if this is a good path, record it
-                if current == end {
-                    // println!("found end, going to save the path");
-                    paths.insert(visited.clone());
+                "shape=ellipse"
+            }];
+            if id == graph.start || id == graph.end {
+                attributes.push("peripheries=2");
+            }
+            if on_path.contains(&id) {
+                attributes.push(HIGHLIGHT);
+            }
+            writeln!(f, "    {} [{}];", quoted(&cave.name), attributes.join(", "))?;
+        }
+        let busiest = match self.overlay {
+            Some(Overlay::Usage(usage)) => usage.values().copied().max().unwrap_or(0),
+            _ => 0,
+        };
+        for (a, b) in graph.passages() {
+            let mut attributes = String::new();
+            match self.overlay {
+                Some(Overlay::Path(_)) if path_passages.contains(&(a, b)) => {
+                    write!(attributes, "{}, penwidth=2", HIGHLIGHT)?;
                 }
-
-                //now backtrack, if possible - if not - return:
-                let dropped_node = visited.pop().unwrap();
-                if visited.len() == 0 {
-                    return paths;
+                Some(Overlay::Usage(usage)) => {
+                    let used = usage.get(&(a, b)).copied().unwrap_or(0);
+                    let width = 1.0 + MAX_EXTRA_WIDTH * used as f64 / busiest.max(1) as f64;
+                    write!(attributes, "label={}, penwidth={:.1}", used, width)?;
                 }
-
-                // index of the next element
-                let last_node = visited.last().unwrap();
-                next_index = self
-                    .edges
-                    .get(last_node)
-                    .unwrap()
-                    .iter()
-                    .position(|x| x == &dropped_node)
-                    .unwrap()
-                    + 1;
+                _ => {}
+            }
+            write!(
+                f,
+                "    {} -- {}",
+                quoted(&graph.caves[a].name),
+                quoted(&graph.caves[b].name)
+            )?;
+            if !attributes.is_empty() {
+                write!(f, " [{}]", attributes)?;
             }
+            writeln!(f, ";")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Which caves a path may go through. A policy keeps whatever it needs to
+/// know about the path so far in its state, and updates it one cave at a
+/// time, so that checking a step does not mean going over the whole path.
+pub trait VisitPolicy {
+    /// Two paths in the same cave with equal states must be allowed the same
+    /// ways to go on, since paths are counted by state.
+    type State: Clone + Eq + Hash;
+
+    /// The state before entering the start cave.
+    fn start(&self, graph: &Graph) -> Self::State;
+
+    /// The state after entering `cave`, or `None` if the path may not go
+    /// there.
+    fn enter(&self, graph: &Graph, state: &Self::State, cave: CaveId) -> Option<Self::State>;
+}
+
+/// Small caves may only be visited once. The state is the small caves
+/// visited so far.
+#[derive(Debug, Clone, Copy)]
+pub struct SmallOnce;
+
+impl VisitPolicy for SmallOnce {
+    type State = CaveSet;
+
+    fn start(&self, _: &Graph) -> CaveSet {
+        CaveSet::default()
+    }
+
+    fn enter(&self, graph: &Graph, visited: &CaveSet, cave: CaveId) -> Option<CaveSet> {
+        if graph.cave(cave).big {
+            return Some(visited.clone());
+        }
+        if visited.contains(cave) {
+            return None;
+        }
+        return Some(visited.with(cave));
+    }
+}
+
+/// Small caves may be visited once, except for a single one that may be
+/// visited twice. The state is the small caves visited so far, and whether
+/// one of them has been visited twice.
+#[derive(Debug, Clone, Copy)]
+pub struct OneSmallTwice;
+
+impl VisitPolicy for OneSmallTwice {
+    type State = (CaveSet, bool);
+
+    fn start(&self, _: &Graph) -> (CaveSet, bool) {
+        (CaveSet::default(), false)
+    }
+
+    fn enter(
+        &self,
+        graph: &Graph,
+        state: &(CaveSet, bool),
+        cave: CaveId,
+    ) -> Option<(CaveSet, bool)> {
+        let (visited, twice) = state;
+        match SmallOnce.enter(graph, visited, cave) {
+            Some(visited) => Some((visited, *twice)),
+            None if !twice => Some((visited.clone(), true)),
+            None => None,
+        }
+    }
+}
+
+/// Every small cave may be visited up to this many times. The state is how
+/// many times each cave has been visited.
+#[derive(Debug, Clone, Copy)]
+pub struct SmallUpTo(pub u8);
+
+impl VisitPolicy for SmallUpTo {
+    type State = Vec<u8>;
+
+    fn start(&self, graph: &Graph) -> Vec<u8> {
+        vec![0; graph.caves.len()]
+    }
+
+    fn enter(&self, graph: &Graph, visits: &Vec<u8>, cave: CaveId) -> Option<Vec<u8>> {
+        if graph.cave(cave).big {
+            return Some(visits.clone());
+        }
+        if visits[cave] >= self.0 {
+            return None;
         }
+        let mut visits = visits.clone();
+        visits[cave] += 1;
+        return Some(visits);
     }
 }
 
-pub(crate) fn day12part1() -> usize {
-    let mut lines = read_lines("input12.t").unwrap();
-    let g = Graph::from_lines(lines);
-    println!("{:?}", g);
-    let paths = g.all_paths("start".to_string(), "end".to_string(), |ext, visited| {
-        &ext.to_uppercase() == ext || !visited.contains(ext)
-    });
-    println!("{:?}", paths);
-    return paths.len();
+/// Some caves may not be visited at all, and the others as `inner` allows.
+#[derive(Debug, Clone)]
+pub struct Forbidden<P> {
+    caves: CaveSet,
+    inner: P,
 }
 
-pub(crate) fn day12part2() -> usize {
-    let mut lines = read_lines("input12.t").unwrap();
-    let g = Graph::from_lines(lines);
-    fn validation(ext: &String, visited: &Vec<String>) -> bool {
-        if ext.chars().all(char::is_uppercase) {
-            return true;
+impl<P: VisitPolicy> Forbidden<P> {
+    /// Forbids the caves with these names. Names of caves that are not in
+    /// the graph are ignored, as no path goes there anyway.
+    pub fn new(graph: &Graph, names: &[&str], inner: P) -> Self {
+        let mut caves = CaveSet::default();
+        for id in names.iter().filter_map(|name| graph.id(name)) {
+            caves.insert(id);
         }
+        return Forbidden { caves, inner };
+    }
+}
+
+impl<P: VisitPolicy> VisitPolicy for Forbidden<P> {
+    type State = P::State;
+
+    fn start(&self, graph: &Graph) -> P::State {
+        self.inner.start(graph)
+    }
 
-        if !visited.contains(ext) {
-            return true;
+    fn enter(&self, graph: &Graph, state: &P::State, cave: CaveId) -> Option<P::State> {
+        if self.caves.contains(cave) {
+            return None;
         }
+        return self.inner.enter(graph, state, cave);
+    }
+}
+
+/// Counts the paths through the cave system.
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
 
-        // if we got here, it's a small cave and already appears, check that there's no small cave that appears twice
-        let small_caves = visited
+    type Parsed = Graph;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Graph::from_lines(input)
+    }
+
+    fn part1(g: &Graph) -> usize {
+        debug!("{}", g.dot(None));
+        return g.count_paths(&SmallOnce);
+    }
+
+    fn part2(g: &Graph) -> usize {
+        return g.count_paths(&OneSmallTwice);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+    const LARGER_EXAMPLE: &str = "dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(Day12::part1(&Day12::parse(SMALL_EXAMPLE).unwrap()), 10);
+        assert_eq!(Day12::part1(&Day12::parse(LARGER_EXAMPLE).unwrap()), 19);
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(Day12::part2(&Day12::parse(SMALL_EXAMPLE).unwrap()), 36);
+        assert_eq!(Day12::part2(&Day12::parse(LARGER_EXAMPLE).unwrap()), 103);
+    }
+
+    fn assert_counts_match<P: VisitPolicy>(g: &Graph, policy: &P) -> usize {
+        let count = g.count_paths(policy);
+        assert_eq!(count, g.all_paths(policy).len());
+        return count;
+    }
+
+    #[test]
+    fn counting_matches_enumeration() {
+        for example in [SMALL_EXAMPLE, LARGER_EXAMPLE] {
+            let g = Day12::parse(example).unwrap();
+            let once = assert_counts_match(&g, &SmallOnce);
+            let one_twice = assert_counts_match(&g, &OneSmallTwice);
+            assert_eq!(assert_counts_match(&g, &SmallUpTo(1)), once);
+            assert!(assert_counts_match(&g, &SmallUpTo(2)) > one_twice);
+        }
+    }
+
+    #[test]
+    fn enumerates_paths() {
+        let g = Day12::parse(SMALL_EXAMPLE).unwrap();
+        let paths = g.all_paths(&SmallOnce);
+        let named: Vec<Vec<&str>> = paths.iter().map(|path| g.names(path)).collect();
+        assert!(named.contains(&"start,A,b,A,c,A,end".split(',').collect()));
+    }
+
+    #[test]
+    fn forbidden_caves() {
+        let g = Day12::parse(SMALL_EXAMPLE).unwrap();
+        let c = g.id("c").unwrap();
+        let without_c = g
+            .all_paths(&SmallOnce)
             .iter()
-            .filter(|&x| x.chars().all(char::is_lowercase))
-            .collect::<Vec<_>>();
-        let unique_caves = HashSet::<&&String>::from_iter(small_caves.iter());
+            .filter(|path| !path.contains(&c))
+            .count();
+        let policy = Forbidden::new(&g, &["c", "no such cave"], SmallOnce);
+        assert_eq!(assert_counts_match(&g, &policy), without_c);
+        assert_eq!(g.count_paths(&Forbidden::new(&g, &["start"], SmallOnce)), 0);
+    }
+
+    #[test]
+    fn draws_caves() {
+        let g = Day12::parse(SMALL_EXAMPLE).unwrap();
+        let dot = g.dot(None).to_string();
+        assert!(dot.starts_with("graph caves {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("    \"start\" [shape=ellipse, peripheries=2];\n"));
+        assert!(dot.contains("    \"A\" [shape=box];\n"));
+        assert!(dot.contains("    \"start\" -- \"A\";\n"));
+        assert_eq!(dot.matches(" -- ").count(), 7);
+    }
+
+    #[test]
+    fn draws_overlays() {
+        let g = Day12::parse(SMALL_EXAMPLE).unwrap();
+        let path: Vec<CaveId> = ["start", "b", "end"]
+            .iter()
+            .map(|name| g.id(name).unwrap())
+            .collect();
+        let dot = g.dot(Some(Overlay::Path(&path))).to_string();
+        assert!(dot.contains("\"b\" [shape=ellipse, color=red, fontcolor=red];"));
+        assert!(dot.contains("\"b\" -- \"end\" [color=red, fontcolor=red, penwidth=2];"));
+        assert!(dot.contains("\"A\" -- \"end\";"));
+
+        let usage = Graph::passage_usage(&g.all_paths(&SmallOnce));
+        let dot = g.dot(Some(Overlay::Usage(&usage))).to_string();
+        // b-d is a dead end, and the paths through c use A-c twice
+        assert!(dot.contains("\"b\" -- \"d\" [label=0, penwidth=1.0];"));
+        assert!(dot.contains("\"A\" -- \"c\" [label=10, penwidth=5.0];"));
+        assert!(dot.contains("\"A\" -- \"end\" [label=7, penwidth=3.8];"));
+    }
+
+    #[test]
+    fn quotes_names() {
+        assert_eq!(quoted("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
 
-        if unique_caves.len() == small_caves.len() {
-            return true;
+    #[test]
+    fn any_number_of_caves() {
+        // a chain of 100 small caves, with a big one beside each
+        let mut lines = vec!["start-c0".to_owned(), "c99-end".to_owned()];
+        for i in 0..100 {
+            lines.push(format!("c{}-B{}", i, i));
+            if i > 0 {
+                lines.push(format!("c{}-c{}", i - 1, i));
+            }
         }
+        let g = Day12::parse(&lines.join("\n")).unwrap();
+        // a big cave only leads back to the small cave just visited
+        assert_eq!(g.count_paths(&SmallOnce), 1);
+        assert_eq!(g.all_paths(&SmallOnce)[0].len(), 102);
+        let mut set = CaveSet::default();
+        set.insert(130);
+        assert!(set.contains(130) && !set.contains(66) && !set.contains(1000));
+        assert_eq!(set, CaveSet::default().with(130));
+    }
+
+    #[test]
+    fn rejects_joined_big_caves() {
+        let err = Day12::parse("start-A\nA-B\nB-end").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("line 2, column 1: two big caves"));
+    }
+
+    #[test]
+    fn interns_caves() {
+        let g = Day12::parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(g.cave(g.start).name, "start");
+        assert_eq!(g.cave(g.end).name, "end");
+        let a = g.id("A").unwrap();
+        assert!(g.cave(a).big);
+        assert!(!g.cave(g.id("b").unwrap()).big);
+        assert_eq!(g.id("z"), None);
+    }
 
-        return false;
+    #[test]
+    fn rejects_missing_start() {
+        assert!(Day12::parse("a-end").is_err());
     }
-    let paths = g.all_paths("start".to_string(), "end".to_string(), validation);
-    return paths.len();
 }
@@ -1,82 +1,117 @@
+//! Day 13: Transparent Origami
+
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{HashSet, VecDeque},
     fmt::{Display, Write},
-    fs::File,
-    io::{self, BufRead, BufReader, Lines, Read},
-    num::ParseIntError,
-    ops::Add,
-    path::Path,
-    thread::current,
 };
 
-fn parse_line(s: &str) -> Result<(i32, i32), ParseIntError> {
-    let mut pair = s.split(',').collect::<Vec<&str>>();
-    Ok((pair[0].to_string().parse()?, pair[1].to_string().parse()?))
+use crate::debug;
+use crate::error::{parse_field, ParseError};
+use crate::input::{sections, Line};
+use crate::ocr;
+use crate::solution::Solution;
+
+// coordinates are parsed unsigned, as the paper has no dots left of or above
+// the origin, and small enough that folding them never overflows
+fn parse_line(s: &str) -> Result<(i32, i32), ParseError> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| ParseError::new(s.len() + 1, "expected a dot as 'x,y'"))?;
+    Ok((
+        parse_field::<u16>(x, 1, "an x coordinate")?.into(),
+        parse_field::<u16>(y, x.len() + 2, "a y coordinate")?.into(),
+    ))
 }
 
-fn read_buffer<P>(filename: P) -> io::Result<BufReader<File>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file))
+fn parse_fold(s: &str) -> Result<(char, i32), ParseError> {
+    const PREFIX: &str = "fold along ";
+    let fold = s
+        .strip_prefix(PREFIX)
+        .ok_or_else(|| ParseError::new(1, format!("expected '{}'", PREFIX.trim_end())))?;
+    let column = PREFIX.len() + 1;
+    let (axis, index) = fold
+        .split_once('=')
+        .ok_or_else(|| ParseError::new(column, "expected a fold as 'x=N' or 'y=N'"))?;
+    let direction = match axis {
+        "x" => 'x',
+        "y" => 'y',
+        other => {
+            let reason = format!("expected the axis x or y, found '{}'", other);
+            return Err(ParseError::new(column, reason));
+        }
+    };
+    let index: u16 = parse_field(index, column + axis.len() + 1, "a fold line")?;
+    Ok((direction, index.into()))
 }
 
-#[derive(Debug)]
-struct TransparentPaper {
-    dots: HashSet<(i32, i32)>,
+pub type Dot = (i32, i32);
+
+/// The dots on the transparent paper and the folds still to be made.
+#[derive(Debug, Clone)]
+pub struct TransparentPaper {
+    dots: HashSet<Dot>,
     folds: VecDeque<(char, i32)>,
 }
 
 impl TransparentPaper {
-    fn from_lines(buf: &mut BufReader<File>) -> Self {
+    fn from_sections(sections: Vec<Vec<Line>>) -> Result<Self, ParseError> {
         let mut paper = TransparentPaper {
             dots: HashSet::new(),
             folds: VecDeque::new(),
         };
 
-        for line in buf.lines() {
-            if let Ok(coord) = parse_line(&line.unwrap()) {
-                paper.dots.insert(coord);
-            } else {
-                break; // this skips the empty line between dots and folds
-            }
+        // the dots, then a blank line, then the folds
+        let mut sections = sections.into_iter();
+        for line in sections.next().unwrap_or_default() {
+            let coord = line.parse(parse_line)?;
+            paper.dots.insert(coord);
+        }
+
+        for line in sections.flatten() {
+            let fold = line.parse(parse_fold)?;
+            paper.folds.push_back(fold);
         }
 
-        for line in buf.lines() {
-            let unwrapped = line.unwrap();
-            let mut parts = unwrapped.split(&[' ', '='][..]).rev();
-            let index = parts.next().unwrap().parse::<i32>().unwrap();
-            let direction = parts.next().unwrap().chars().next().unwrap();
-            paper.folds.push_back((direction, index));
+        if paper.dots.is_empty() {
+            return Err(ParseError::new(1, "the paper has no dots"));
+        }
+        if paper.folds.is_empty() {
+            return Err(ParseError::new(
+                1,
+                "expected fold instructions after the dots and a blank line",
+            ));
         }
 
-        return paper;
+        return Ok(paper);
     }
 
-    fn fold_once(&mut self) {
-        let f = self.folds.pop_front().unwrap();
+    /// Makes the next fold, returning whether there was one left to make.
+    pub fn fold_once(&mut self) -> bool {
+        let f = match self.folds.pop_front() {
+            Some(f) => f,
+            None => return false,
+        };
 
-        let final_fold: Box<dyn Fn((i32, i32)) -> (i32, i32)> = match f.0 {
+        let final_fold: Box<dyn Fn(Dot) -> Dot> = match f.0 {
             'x' => Box::new(|c| (f.1 - (c.0 - f.1).abs(), c.1)),
             'y' => Box::new(|c| (c.0, f.1 - (c.1 - f.1).abs())),
-            _ => return,
+            _ => return true,
         };
 
         self.dots = self.dots.iter().map(|&c| final_fold(c)).collect();
+        return true;
     }
 
-    fn fold_all(&mut self) {
-        while self.folds.len() > 0 {
-            self.fold_once();
-        }
+    /// Makes every remaining fold.
+    pub fn fold_all(&mut self) {
+        while self.fold_once() {}
     }
 }
 
 impl Display for TransparentPaper {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_x = self.dots.iter().map(|(x, y)| x).max().unwrap();
-        let max_y = self.dots.iter().map(|(x, y)| y).max().unwrap();
+        let max_x = self.dots.iter().map(|(x, _)| x).max().unwrap();
+        let max_y = self.dots.iter().map(|(_, y)| y).max().unwrap();
         for y in 0..max_y + 1 {
             for x in 0..max_x + 1 {
                 if self.dots.contains(&(x, y)) {
@@ -92,10 +127,91 @@ impl Display for TransparentPaper {
     }
 }
 
-pub(crate) fn day13part1() -> usize {
-    let mut buf = read_buffer("input13.t").unwrap();
-    let mut p = TransparentPaper::from_lines(buf.by_ref());
-    p.fold_all();
-    println!("{}", p);
-    return p.dots.len();
+/// Folds the transparent paper along its fold instructions.
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Parsed = TransparentPaper;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        TransparentPaper::from_sections(sections(input))
+    }
+
+    fn part1(p: &TransparentPaper) -> usize {
+        let mut p = p.clone();
+        p.fold_once();
+        return p.dots.len();
+    }
+
+    fn part2(p: &TransparentPaper) -> String {
+        let mut p = p.clone();
+        p.fold_all();
+        debug!("{}", p);
+        return ocr::recognize(&p.dots);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+    #[test]
+    fn part1_example() {
+        let paper = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part1(&paper), 17);
+    }
+
+    #[test]
+    fn all_folds_example() {
+        let mut paper = Day13::parse(EXAMPLE).unwrap();
+        paper.fold_all();
+        assert_eq!(paper.dots.len(), 16);
+        assert_eq!(paper.to_string(), "#####\n#...#\n#...#\n#...#\n#####\n");
+    }
+
+    #[test]
+    fn rejects_missing_folds() {
+        let err = Day13::parse("6,10\n\n").unwrap_err();
+        assert!(err.to_string().contains("expected fold instructions"));
+        let mut paper = Day13::parse("6,10\n\nfold along y=7").unwrap();
+        assert!(paper.fold_once());
+        assert!(!paper.fold_once());
+    }
+
+    #[test]
+    fn rejects_negative_coordinates() {
+        let err = Day13::parse("-5,1\n\nfold along x=0").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(1, "expected an x coordinate, found '-5'").at_line(1, "-5,1")
+        );
+        assert!(Day13::parse("5,-1\n\nfold along x=0").is_err());
+        assert!(Day13::parse("5,1\n\nfold along x=-2").is_err());
+    }
 }
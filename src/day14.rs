@@ -0,0 +1,164 @@
+//! Day 14: Extended Polymerization
+
+use std::collections::HashMap;
+
+use crate::error::ParseError;
+use crate::input::{sections, Line};
+use crate::solution::Solution;
+
+fn parse_rule(s: &str) -> Result<((char, char), char), ParseError> {
+    let (pair, insert) = s
+        .split_once(" -> ")
+        .ok_or_else(|| ParseError::new(s.len() + 1, "expected a rule as 'AB -> C'"))?;
+    let pair: Vec<char> = pair.chars().collect();
+    if pair.len() != 2 {
+        return Err(ParseError::new(1, "expected a pair of two elements"));
+    }
+    let mut insert_chars = insert.chars();
+    match (insert_chars.next(), insert_chars.next()) {
+        (Some(element), None) => Ok(((pair[0], pair[1]), element)),
+        _ => Err(ParseError::new(
+            s.len() - insert.len() + 1,
+            "expected a single element to insert",
+        )),
+    }
+}
+
+/// The polymer template and the pair insertion rules.
+#[derive(Debug, Clone)]
+pub struct Polymer {
+    template: Vec<char>,
+    rules: HashMap<(char, char), char>,
+}
+
+impl Polymer {
+    fn from_sections(sections: Vec<Vec<Line>>) -> Result<Self, ParseError> {
+        let mut sections = sections.into_iter();
+        let template: Vec<char> = match sections.next() {
+            Some(lines) if lines.len() == 1 => lines[0].text.chars().collect(),
+            Some(lines) => {
+                let reason = "expected a blank line after the template";
+                return Err(lines[1].error(ParseError::new(1, reason)));
+            }
+            None => return Err(ParseError::new(1, "expected a template, found nothing")),
+        };
+        let mut rules = HashMap::new();
+        for line in sections.flatten() {
+            let (pair, element) = line.parse(parse_rule)?;
+            rules.insert(pair, element);
+        }
+        return Ok(Polymer { template, rules });
+    }
+
+    /// How many times each element appears after `steps` steps of insertion.
+    /// Only the counts of each pair are tracked, as the polymer itself doubles
+    /// in length every step.
+    pub fn element_counts(&self, steps: usize) -> HashMap<char, usize> {
+        let mut pairs: HashMap<(char, char), usize> = HashMap::new();
+        for pair in self.template.windows(2) {
+            *pairs.entry((pair[0], pair[1])).or_insert(0) += 1;
+        }
+
+        for _ in 0..steps {
+            let mut next = HashMap::new();
+            for (&(a, b), &count) in &pairs {
+                match self.rules.get(&(a, b)) {
+                    Some(&c) => {
+                        *next.entry((a, c)).or_insert(0) += count;
+                        *next.entry((c, b)).or_insert(0) += count;
+                    }
+                    None => *next.entry((a, b)).or_insert(0) += count,
+                }
+            }
+            pairs = next;
+        }
+
+        // every element is the first of a pair, except for the last one
+        let mut counts = HashMap::new();
+        for (&(a, _), &count) in &pairs {
+            *counts.entry(a).or_insert(0) += count;
+        }
+        if let Some(&last) = self.template.last() {
+            *counts.entry(last).or_insert(0) += 1;
+        }
+        return counts;
+    }
+
+    /// The most common element's count minus the least common one's.
+    pub fn spread(&self, steps: usize) -> usize {
+        let counts = self.element_counts(steps);
+        let max = counts.values().max().unwrap_or(&0);
+        let min = counts.values().min().unwrap_or(&0);
+        return max - min;
+    }
+}
+
+/// Grows the submarine's polymer from pair insertion rules.
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Parsed = Polymer;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Polymer::from_sections(sections(input))
+    }
+
+    fn part1(polymer: &Polymer) -> usize {
+        return polymer.spread(10);
+    }
+
+    fn part2(polymer: &Polymer) -> usize {
+        return polymer.spread(40);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    #[test]
+    fn part1_example() {
+        let polymer = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part1(&polymer), 1588);
+    }
+
+    #[test]
+    fn part2_example() {
+        let polymer = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part2(&polymer), 2188189693529);
+    }
+
+    #[test]
+    fn element_counts_after_one_step() {
+        // NNCB becomes NCNBCHB
+        let polymer = Day14::parse(EXAMPLE).unwrap();
+        let counts = polymer.element_counts(1);
+        assert_eq!(counts[&'N'], 2);
+        assert_eq!(counts[&'C'], 2);
+        assert_eq!(counts[&'B'], 2);
+        assert_eq!(counts[&'H'], 1);
+    }
+}
@@ -0,0 +1,134 @@
+//! Day 15: Chiton
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::error::ParseError;
+use crate::grid::{Grid, Point};
+use crate::solution::Solution;
+
+/// The risk level of every position in the cave.
+#[derive(Debug, Clone)]
+pub struct RiskMap {
+    risks: Grid<u8>,
+}
+
+impl RiskMap {
+    /// The map repeated `times` times in both directions, where every repeat
+    /// to the right or down adds one to the risk, wrapping from 9 back to 1.
+    pub fn tiled(&self, times: usize) -> RiskMap {
+        let (width, height) = (self.risks.width(), self.risks.height());
+        let mut cells = Vec::with_capacity(width * height * times * times);
+        for y in 0..height * times {
+            for x in 0..width * times {
+                let risk = self.risks[(x % width, y % height)] as usize + x / width + y / height;
+                cells.push(((risk - 1) % 9 + 1) as u8);
+            }
+        }
+        return RiskMap {
+            risks: Grid::new(width * times, height * times, cells),
+        };
+    }
+
+    /// The lowest total risk of any path from the top left to the bottom
+    /// right, not counting the risk of the starting position.
+    pub fn lowest_total_risk(&self) -> usize {
+        let start = (0, 0);
+        let end = (self.risks.width() - 1, self.risks.height() - 1);
+        let mut best: HashMap<Point, usize> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        best.insert(start, 0);
+        queue.push(Reverse((0, start)));
+
+        while let Some(Reverse((risk, point))) = queue.pop() {
+            if point == end {
+                return risk;
+            }
+            if risk > best[&point] {
+                continue;
+            }
+            for next in self.risks.orthogonal_neighbors(point) {
+                let next_risk = risk + self.risks[next] as usize;
+                if best.get(&next).is_none_or(|&known| next_risk < known) {
+                    best.insert(next, next_risk);
+                    queue.push(Reverse((next_risk, next)));
+                }
+            }
+        }
+
+        return usize::MAX;
+    }
+}
+
+/// Finds the safest path through the chitons.
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Parsed = RiskMap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let risks = Grid::parse_chars(input, "a risk from 1 to 9", |c| match c {
+            '1'..='9' => c.to_digit(10).map(|d| d as u8),
+            _ => None,
+        })?;
+        return Ok(RiskMap { risks });
+    }
+
+    fn part1(map: &RiskMap) -> usize {
+        return map.lowest_total_risk();
+    }
+
+    fn part2(map: &RiskMap) -> usize {
+        return map.tiled(5).lowest_total_risk();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    #[test]
+    fn part1_example() {
+        let map = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part1(&map), 40);
+    }
+
+    #[test]
+    fn part2_example() {
+        let map = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part2(&map), 315);
+    }
+
+    #[test]
+    fn tiling_wraps_risks() {
+        let map = Day15::parse("8").unwrap().tiled(3);
+        assert_eq!(map.risks.row(0), [8, 9, 1]);
+        assert_eq!(map.risks.row(2), [1, 2, 3]);
+    }
+
+    #[test]
+    fn rejects_zero_risk() {
+        let err = Day15::parse("01\n11").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(1, "expected a risk from 1 to 9, found '0'").at_line(1, "01")
+        );
+    }
+}
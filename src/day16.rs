@@ -0,0 +1,224 @@
+//! Day 16: Packet Decoder
+
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
+
+/// A decoded BITS packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub contents: Contents,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contents {
+    /// Type 4: a number.
+    Literal(u64),
+    /// Any other type: an operation on the sub-packets.
+    Operator { type_id: u8, packets: Vec<Packet> },
+}
+
+impl Packet {
+    /// The sum of this packet's version and those of every packet inside it.
+    pub fn version_sum(&self) -> u64 {
+        let inner = match &self.contents {
+            Contents::Literal(_) => 0,
+            Contents::Operator { packets, .. } => packets.iter().map(Packet::version_sum).sum(),
+        };
+        return self.version as u64 + inner;
+    }
+
+    /// Evaluates the expression the packet represents.
+    pub fn value(&self) -> u64 {
+        let (type_id, packets) = match &self.contents {
+            Contents::Literal(value) => return *value,
+            Contents::Operator { type_id, packets } => (*type_id, packets),
+        };
+        let mut values = packets.iter().map(Packet::value);
+        match type_id {
+            0 => values.sum(),
+            1 => values.product(),
+            2 => values.min().unwrap_or(0),
+            3 => values.max().unwrap_or(0),
+            _ => {
+                let (a, b) = (values.next().unwrap_or(0), values.next().unwrap_or(0));
+                let holds = match type_id {
+                    5 => a > b,
+                    6 => a < b,
+                    _ => a == b,
+                };
+                holds as u64
+            }
+        }
+    }
+}
+
+/// Reads the bits of a transmission, most significant bit first.
+struct BitReader {
+    bits: Vec<u8>,
+    position: usize,
+}
+
+impl BitReader {
+    fn read(&mut self, count: usize) -> Result<u64, ParseError> {
+        if self.position + count > self.bits.len() {
+            let reason = format!(
+                "the transmission ends inside a packet, at bit {}",
+                self.position
+            );
+            // four bits to a hex digit
+            return Err(ParseError::new(self.position / 4 + 1, reason));
+        }
+        let value = self.bits[self.position..self.position + count]
+            .iter()
+            .fold(0, |acc, &bit| (acc << 1) | bit as u64);
+        self.position += count;
+        return Ok(value);
+    }
+
+    fn packet(&mut self) -> Result<Packet, ParseError> {
+        let version = self.read(3)? as u8;
+        let type_id = self.read(3)? as u8;
+        if type_id == 4 {
+            let mut value = 0;
+            loop {
+                let group = self.read(5)?;
+                value = (value << 4) | (group & 0xf);
+                if group & 0x10 == 0 {
+                    break;
+                }
+            }
+            return Ok(Packet {
+                version,
+                contents: Contents::Literal(value),
+            });
+        }
+
+        let mut packets = Vec::new();
+        if self.read(1)? == 0 {
+            let length = self.read(15)? as usize;
+            let end = self.position + length;
+            while self.position < end {
+                packets.push(self.packet()?);
+            }
+            if self.position != end {
+                let reason = format!(
+                    "the sub-packets take {} bits, more than the {} given for them",
+                    self.position + length - end,
+                    length
+                );
+                return Err(ParseError::new(end / 4 + 1, reason));
+            }
+        } else {
+            let count = self.read(11)?;
+            for _ in 0..count {
+                packets.push(self.packet()?);
+            }
+        }
+        return Ok(Packet {
+            version,
+            contents: Contents::Operator { type_id, packets },
+        });
+    }
+}
+
+/// Decodes the outermost packet of a hexadecimal transmission.
+pub fn decode(hex: &str) -> Result<Packet, ParseError> {
+    let mut bits = Vec::with_capacity(hex.len() * 4);
+    for (idx, c) in hex.chars().enumerate() {
+        let digit = c.to_digit(16).ok_or_else(|| {
+            ParseError::new(
+                idx + 1,
+                format!("expected a hexadecimal digit, found '{}'", c),
+            )
+        })?;
+        bits.extend((0..4).rev().map(|shift| ((digit >> shift) & 1) as u8));
+    }
+    return BitReader { bits, position: 0 }.packet();
+}
+
+/// Decodes the BITS transmission from the elves.
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Parsed = Packet;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        match lines(input).next() {
+            Some(line) => line.parse(|text| decode(text.trim_end())),
+            None => Err(ParseError::new(1, "expected a transmission, found nothing")),
+        }
+    }
+
+    fn part1(packet: &Packet) -> u64 {
+        return packet.version_sum();
+    }
+
+    fn part2(packet: &Packet) -> u64 {
+        return packet.value();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_literal() {
+        let packet = decode("D2FE28").unwrap();
+        assert_eq!(packet.version, 6);
+        assert_eq!(packet.contents, Contents::Literal(2021));
+    }
+
+    #[test]
+    fn part1_examples() {
+        for (hex, sum) in [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ] {
+            assert_eq!(Day16::part1(&Day16::parse(hex).unwrap()), sum, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn part2_examples() {
+        for (hex, value) in [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ] {
+            assert_eq!(Day16::part2(&Day16::parse(hex).unwrap()), value, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn truncated_transmission() {
+        assert!(decode("D2F").is_err());
+    }
+
+    #[test]
+    fn sub_packets_past_their_length() {
+        // the first operator example, claiming 26 bits of sub-packets for 27
+        assert!(decode("38006F45291200").is_ok());
+        let err = decode("38006B45291200").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                13,
+                "the sub-packets take 27 bits, more than the 26 given for them"
+            )
+        );
+    }
+}
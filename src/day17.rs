@@ -0,0 +1,148 @@
+//! Day 17: Trick Shot
+
+use crate::error::{parse_field, ParseError};
+use crate::input::lines;
+use crate::solution::Solution;
+
+/// The target area the probe has to end up in, bounds included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Target {
+    pub x: (i32, i32),
+    pub y: (i32, i32),
+}
+
+fn parse_range(s: &str, axis: &str, column: usize) -> Result<(i32, i32), ParseError> {
+    let range = s.strip_prefix(axis).ok_or_else(|| {
+        ParseError::new(
+            column,
+            format!("expected the {} range", axis.trim_end_matches('=')),
+        )
+    })?;
+    let (from, to) = range
+        .split_once("..")
+        .ok_or_else(|| ParseError::new(column, "expected a range as 'from..to'"))?;
+    let from_column = column + axis.len();
+    let from: i32 = parse_field(from, from_column, "a number")?;
+    let to: i32 = parse_field(to, from_column + from.to_string().len() + 2, "a number")?;
+    return Ok((from.min(to), from.max(to)));
+}
+
+fn parse_target(s: &str) -> Result<Target, ParseError> {
+    const PREFIX: &str = "target area: ";
+    let ranges = s
+        .strip_prefix(PREFIX)
+        .ok_or_else(|| ParseError::new(1, format!("expected '{}'", PREFIX.trim_end())))?;
+    let (x, y) = ranges
+        .split_once(", ")
+        .ok_or_else(|| ParseError::new(s.len() + 1, "expected 'x=..., y=...'"))?;
+    let x_column = PREFIX.len() + 1;
+    let target = Target {
+        x: parse_range(x, "x=", x_column)?,
+        y: parse_range(y, "y=", x_column + x.len() + 2)?,
+    };
+    if target.x.0 <= 0 || target.y.1 >= 0 {
+        return Err(ParseError::new(
+            x_column,
+            "expected the target ahead of and below the probe",
+        ));
+    }
+    return Ok(target);
+}
+
+impl Target {
+    /// Whether a probe launched with this velocity is ever inside the target
+    /// at the end of a step, and if so the highest it gets.
+    pub fn launch(&self, mut dx: i32, mut dy: i32) -> Option<i32> {
+        let (mut x, mut y, mut highest) = (0, 0, 0);
+        while x <= self.x.1 && y >= self.y.0 {
+            x += dx;
+            y += dy;
+            dx -= dx.signum();
+            dy -= 1;
+            highest = highest.max(y);
+            if (self.x.0..=self.x.1).contains(&x) && (self.y.0..=self.y.1).contains(&y) {
+                return Some(highest);
+            }
+        }
+        return None;
+    }
+
+    /// The highest point of every launch that hits the target. Launching
+    /// faster than the far edge overshoots in one step, as does launching up
+    /// faster than the bottom edge, since the probe comes back down through 0
+    /// at the speed it went up.
+    pub fn hits(&self) -> Vec<i32> {
+        let mut hits = Vec::new();
+        for dx in 1..=self.x.1 {
+            for dy in self.y.0..=-self.y.0 {
+                if let Some(highest) = self.launch(dx, dy) {
+                    hits.push(highest);
+                }
+            }
+        }
+        return hits;
+    }
+}
+
+/// Launches the probe into the ocean trench.
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Parsed = Target;
+    type Part1 = i32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        match lines(input).next() {
+            Some(line) => line.parse(parse_target),
+            None => Err(ParseError::new(
+                1,
+                "expected the target area, found nothing",
+            )),
+        }
+    }
+
+    fn part1(target: &Target) -> i32 {
+        return target.hits().into_iter().max().unwrap_or(0);
+    }
+
+    fn part2(target: &Target) -> usize {
+        return target.hits().len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn parses_target() {
+        let target = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(target.x, (20, 30));
+        assert_eq!(target.y, (-10, -5));
+    }
+
+    #[test]
+    fn launches() {
+        let target = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(target.launch(7, 2), Some(3));
+        assert_eq!(target.launch(6, 9), Some(45));
+        assert_eq!(target.launch(17, -4), None);
+    }
+
+    #[test]
+    fn part1_example() {
+        let target = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::part1(&target), 45);
+    }
+
+    #[test]
+    fn part2_example() {
+        let target = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::part2(&target), 112);
+    }
+}
@@ -0,0 +1,220 @@
+//! Day 18: Snailfish
+
+use std::fmt::Display;
+
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
+
+/// A snailfish number: a regular number, or a pair of snailfish numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Number {
+    Regular(u32),
+    Pair(Box<Number>, Box<Number>),
+}
+
+/// Reads a snailfish number from the front of `s`, returning the rest.
+/// `offset` is how far into the line `s` starts, for error columns.
+fn parse_number(s: &str, offset: usize) -> Result<(Number, &str), ParseError> {
+    let expect = |rest: &str, c: char| -> Result<usize, ParseError> {
+        if rest.starts_with(c) {
+            return Ok(1);
+        }
+        let column = offset + s.len() - rest.len() + 1;
+        return Err(ParseError::new(column, format!("expected '{}'", c)));
+    };
+
+    if let Some(rest) = s.strip_prefix('[') {
+        let (left, rest) = parse_number(rest, offset + s.len() - rest.len())?;
+        let rest = &rest[expect(rest, ',')?..];
+        let (right, rest) = parse_number(rest, offset + s.len() - rest.len())?;
+        let rest = &rest[expect(rest, ']')?..];
+        return Ok((Number::Pair(Box::new(left), Box::new(right)), rest));
+    }
+
+    let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    match s[..digits].parse() {
+        Ok(value) => Ok((Number::Regular(value), &s[digits..])),
+        Err(_) => Err(ParseError::new(offset + 1, "expected '[' or a number")),
+    }
+}
+
+fn parse_line(s: &str) -> Result<Number, ParseError> {
+    let (number, rest) = parse_number(s, 0)?;
+    if !rest.is_empty() {
+        let column = s.len() - rest.len() + 1;
+        return Err(ParseError::new(column, "unexpected text after the number"));
+    }
+    return Ok(number);
+}
+
+impl Number {
+    fn add_leftmost(&mut self, value: u32) {
+        match self {
+            Number::Regular(n) => *n += value,
+            Number::Pair(left, _) => left.add_leftmost(value),
+        }
+    }
+
+    fn add_rightmost(&mut self, value: u32) {
+        match self {
+            Number::Regular(n) => *n += value,
+            Number::Pair(_, right) => right.add_rightmost(value),
+        }
+    }
+
+    /// Explodes the leftmost pair nested inside four pairs, if there is one.
+    /// Returns the values still to be added to the regular numbers on its
+    /// left and right, once the pair has been replaced by 0.
+    fn explode(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+        let (left, right) = match self {
+            Number::Regular(_) => return None,
+            Number::Pair(left, right) => (left, right),
+        };
+        if depth >= 4 {
+            if let (Number::Regular(a), Number::Regular(b)) = (left.as_ref(), right.as_ref()) {
+                let carry = (Some(*a), Some(*b));
+                *self = Number::Regular(0);
+                return Some(carry);
+            }
+        }
+        if let Some((carry_left, carry_right)) = left.explode(depth + 1) {
+            if let Some(value) = carry_right {
+                right.add_leftmost(value);
+            }
+            return Some((carry_left, None));
+        }
+        if let Some((carry_left, carry_right)) = right.explode(depth + 1) {
+            if let Some(value) = carry_left {
+                left.add_rightmost(value);
+            }
+            return Some((None, carry_right));
+        }
+        return None;
+    }
+
+    /// Splits the leftmost regular number of 10 or more, if there is one.
+    fn split(&mut self) -> bool {
+        match self {
+            Number::Regular(n) if *n >= 10 => {
+                let half = *n / 2;
+                *self = Number::Pair(
+                    Box::new(Number::Regular(half)),
+                    Box::new(Number::Regular(*n - half)),
+                );
+                true
+            }
+            Number::Regular(_) => false,
+            Number::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+
+    /// Explodes and splits until neither applies any more.
+    pub fn reduce(&mut self) {
+        while self.explode(0).is_some() || self.split() {}
+    }
+
+    /// The reduced sum of two snailfish numbers.
+    pub fn add(&self, other: &Number) -> Number {
+        let mut sum = Number::Pair(Box::new(self.clone()), Box::new(other.clone()));
+        sum.reduce();
+        return sum;
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        match self {
+            Number::Regular(n) => *n,
+            Number::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Regular(n) => write!(f, "{}", n),
+            Number::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+/// Does the snailfish's math homework.
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Parsed = Vec<Number>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        lines(input).map(|line| line.parse(parse_line)).collect()
+    }
+
+    fn part1(numbers: &Vec<Number>) -> u32 {
+        let sum = numbers.iter().cloned().reduce(|acc, n| acc.add(&n));
+        return sum.map_or(0, |sum| sum.magnitude());
+    }
+
+    fn part2(numbers: &Vec<Number>) -> u32 {
+        let mut largest = 0;
+        for (i, a) in numbers.iter().enumerate() {
+            for (j, b) in numbers.iter().enumerate() {
+                if i != j {
+                    largest = largest.max(a.add(b).magnitude());
+                }
+            }
+        }
+        return largest;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+    fn number(s: &str) -> Number {
+        return parse_line(s).unwrap();
+    }
+
+    #[test]
+    fn explodes() {
+        let mut n = number("[[[[[9,8],1],2],3],4]");
+        n.reduce();
+        assert_eq!(n.to_string(), "[[[[0,9],2],3],4]");
+    }
+
+    #[test]
+    fn magnitude() {
+        assert_eq!(number("[[1,2],[[3,4],5]]").magnitude(), 143);
+    }
+
+    #[test]
+    fn part1_example() {
+        let numbers = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part1(&numbers), 4140);
+    }
+
+    #[test]
+    fn part2_example() {
+        let numbers = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part2(&numbers), 3993);
+    }
+
+    #[test]
+    fn rejects_unbalanced() {
+        assert!(Day18::parse("[[1,2],3").is_err());
+    }
+}
@@ -0,0 +1,410 @@
+//! Day 19: Beacon Scanner
+
+use std::collections::{HashMap, HashSet};
+
+use crate::debug;
+use crate::error::{parse_field, ParseError};
+use crate::input::{sections, Line};
+use crate::solution::Solution;
+
+pub type Position = [i32; 3];
+
+/// A rotation matrix, with a single 1 or -1 in each row.
+type Rotation = [[i32; 3]; 3];
+
+/// How many beacons two scanners need to see in common to be aligned.
+const MIN_OVERLAP: usize = 12;
+
+fn parse_position(s: &str) -> Result<Position, ParseError> {
+    let mut position = [0; 3];
+    let mut column = 1;
+    let mut fields = s.split(',');
+    for coordinate in position.iter_mut() {
+        let field = fields
+            .next()
+            .ok_or_else(|| ParseError::new(s.len() + 1, "expected three coordinates"))?;
+        *coordinate = parse_field(field, column, "a coordinate")?;
+        column += field.len() + 1;
+    }
+    if fields.next().is_some() {
+        return Err(ParseError::new(column, "expected only three coordinates"));
+    }
+    return Ok(position);
+}
+
+/// The beacons one scanner can see, relative to itself.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    beacons: Vec<Position>,
+}
+
+impl Scanner {
+    fn from_lines(lines: Vec<Line>) -> Result<Self, ParseError> {
+        let mut lines = lines.into_iter();
+        match lines.next() {
+            Some(header) if header.text.starts_with("--- scanner") => {}
+            Some(header) => {
+                return Err(header.error(ParseError::new(1, "expected a scanner header")))
+            }
+            None => return Err(ParseError::new(1, "expected a scanner")),
+        }
+        let beacons = lines
+            .map(|line| line.parse(parse_position))
+            .collect::<Result<_, _>>()?;
+        return Ok(Scanner { beacons });
+    }
+}
+
+/// The 24 ways a scanner can be facing: every permutation of the axes and
+/// their signs that keeps the coordinate system right-handed.
+fn rotations() -> Vec<Rotation> {
+    const PERMUTATIONS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    let mut rotations = Vec::with_capacity(24);
+    for axes in PERMUTATIONS {
+        for signs in 0..8 {
+            let mut matrix = [[0; 3]; 3];
+            for (row, &axis) in axes.iter().enumerate() {
+                matrix[row][axis] = if signs & (1 << row) == 0 { 1 } else { -1 };
+            }
+            let determinant = matrix[0][0]
+                * (matrix[1][1] * matrix[2][2] - matrix[1][2] * matrix[2][1])
+                - matrix[0][1] * (matrix[1][0] * matrix[2][2] - matrix[1][2] * matrix[2][0])
+                + matrix[0][2] * (matrix[1][0] * matrix[2][1] - matrix[1][1] * matrix[2][0]);
+            if determinant == 1 {
+                rotations.push(matrix);
+            }
+        }
+    }
+    return rotations;
+}
+
+fn rotate(matrix: &Rotation, p: &Position) -> Position {
+    let mut rotated = [0; 3];
+    for (row, value) in rotated.iter_mut().enumerate() {
+        *value = (0..3).map(|col| matrix[row][col] * p[col]).sum();
+    }
+    return rotated;
+}
+
+/// Every beacon relative to the first scanner, and where each scanner is.
+#[derive(Debug, Clone)]
+pub struct OceanMap {
+    pub beacons: HashSet<Position>,
+    pub scanners: Vec<Position>,
+}
+
+impl OceanMap {
+    pub fn largest_distance(&self) -> i32 {
+        let mut largest = 0;
+        for a in &self.scanners {
+            for b in &self.scanners {
+                let distance: i32 = (0..3).map(|i| (a[i] - b[i]).abs()).sum();
+                largest = largest.max(distance);
+            }
+        }
+        return largest;
+    }
+}
+
+/// Finds the rotation (out of `rotations`) and position of `scanner` relative
+/// to the beacons already placed, if it shares enough of them.
+fn align(
+    known: &HashSet<Position>,
+    scanner: &Scanner,
+    rotations: &[Rotation],
+) -> Option<(Vec<Position>, Position)> {
+    for rotation in rotations {
+        let rotated: Vec<Position> = scanner
+            .beacons
+            .iter()
+            .map(|p| rotate(rotation, p))
+            .collect();
+        let mut offsets: HashMap<Position, usize> = HashMap::new();
+        for a in known {
+            for b in &rotated {
+                let offset = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+                let count = offsets.entry(offset).or_insert(0);
+                *count += 1;
+                if *count >= MIN_OVERLAP {
+                    let placed = rotated
+                        .iter()
+                        .map(|p| [p[0] + offset[0], p[1] + offset[1], p[2] + offset[2]])
+                        .collect();
+                    return Some((placed, offset));
+                }
+            }
+        }
+    }
+    return None;
+}
+
+/// Places every scanner relative to the first one, or `None` if some of them
+/// never overlap with the rest.
+pub fn assemble(scanners: &[Scanner]) -> Option<OceanMap> {
+    let first = scanners.first()?;
+    let rotations = rotations();
+    let mut beacons: HashSet<Position> = first.beacons.iter().copied().collect();
+    let mut positions = vec![None; scanners.len()];
+    positions[0] = Some([0, 0, 0]);
+
+    let mut progress = true;
+    while progress && positions.iter().any(Option::is_none) {
+        progress = false;
+        for (idx, scanner) in scanners.iter().enumerate() {
+            if positions[idx].is_some() {
+                continue;
+            }
+            if let Some((placed, position)) = align(&beacons, scanner, &rotations) {
+                debug!("scanner {} is at {:?}", idx, position);
+                beacons.extend(placed);
+                positions[idx] = Some(position);
+                progress = true;
+            }
+        }
+    }
+
+    let scanners = positions.into_iter().collect::<Option<Vec<_>>>()?;
+    return Some(OceanMap { beacons, scanners });
+}
+
+/// Maps the beacons around the trench from the scanners' reports.
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Parsed = OceanMap;
+    type Part1 = usize;
+    type Part2 = i32;
+
+    /// Reads the scanners' reports and places the scanners, which is the
+    /// expensive part, once for both parts.
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let scanners = sections(input)
+            .into_iter()
+            .map(Scanner::from_lines)
+            .collect::<Result<Vec<_>, _>>()?;
+        if scanners.is_empty() {
+            return Err(ParseError::new(1, "expected a scanner, found nothing"));
+        }
+        return assemble(&scanners).ok_or_else(|| {
+            let reason = format!(
+                "some scanners share fewer than {} beacons with the others",
+                MIN_OVERLAP
+            );
+            ParseError::new(1, reason)
+        });
+    }
+
+    fn part1(map: &OceanMap) -> usize {
+        return map.beacons.len();
+    }
+
+    fn part2(map: &OceanMap) -> i32 {
+        return map.largest_distance();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+    /// Twelve beacons seen by a scanner at the origin and one extra each.
+    /// The second scanner sits at (100, -20, 5), rotated a quarter turn
+    /// around the z axis: it sees (x, y, z) as (y, -x, z).
+    fn example() -> String {
+        let shared: Vec<Position> = (0..12)
+            .map(|i| [i * 7 % 13 + 40, i * i % 17 - 8, i * 3 - 20])
+            .collect();
+        let mut first = vec!["--- scanner 0 ---".to_string()];
+        let mut second = vec!["--- scanner 1 ---".to_string()];
+        for p in &shared {
+            first.push(format!("{},{},{}", p[0], p[1], p[2]));
+            let (x, y, z) = (p[0] - 100, p[1] + 20, p[2] - 5);
+            second.push(format!("{},{},{}", y, -x, z));
+        }
+        first.push("-500,300,200".to_string());
+        second.push("600,-400,-300".to_string());
+        return format!("{}\n\n{}", first.join("\n"), second.join("\n"));
+    }
+
+    #[test]
+    fn part1_example() {
+        let map = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::part1(&map), 79);
+    }
+
+    #[test]
+    fn part2_example() {
+        let map = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::part2(&map), 3621);
+    }
+
+    #[test]
+    fn there_are_24_rotations() {
+        let rotations = rotations();
+        assert_eq!(rotations.len(), 24);
+        let images: HashSet<Position> = rotations.iter().map(|r| rotate(r, &[1, 2, 3])).collect();
+        assert_eq!(images.len(), 24);
+    }
+
+    #[test]
+    fn assembles_overlapping_scanners() {
+        let map = Day19::parse(&example()).unwrap();
+        assert_eq!(map.scanners, vec![[0, 0, 0], [100, -20, 5]]);
+        assert!(map.beacons.contains(&[-500, 300, 200]));
+        assert_eq!(Day19::part1(&map), 14);
+        assert_eq!(Day19::part2(&map), 125);
+    }
+
+    #[test]
+    fn scanners_that_never_overlap() {
+        let input = "--- scanner 0 ---\n1,2,3\n\n--- scanner 1 ---\n4,5,6";
+        assert!(Day19::parse(input).is_err());
+        assert!(Day19::parse("").is_err());
+    }
+}
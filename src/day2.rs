@@ -0,0 +1,167 @@
+//! Day 2: Dive!
+
+use crate::error::{parse_field, ParseError};
+use crate::input::lines;
+use crate::solution::Solution;
+use crate::trace;
+
+/// Steers the submarine with `forward`, `down` and `up` commands.
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Parsed = Vec<Command>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        lines(input).map(|line| line.parse(parse_command)).collect()
+    }
+
+    fn part1(commands: &Vec<Command>) -> i64 {
+        return navigate(commands, Navigation::Plain).answer();
+    }
+
+    fn part2(commands: &Vec<Command>) -> i64 {
+        return navigate(commands, Navigation::Aimed).answer();
+    }
+}
+
+/// Which way a [`Command`] moves the submarine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Down,
+    Up,
+}
+
+/// A single line of the planned course, e.g. `forward 5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Command {
+    pub units: i32,
+    pub direction: Direction,
+}
+
+/// How the submarine interprets its commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Navigation {
+    /// `down` and `up` change the depth directly (part 1).
+    Plain,
+    /// `down` and `up` change the aim, and `forward` travels along it (part 2).
+    Aimed,
+}
+
+/// The submarine's position, along with the aim that `down` and `up` adjust.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Location {
+    pub horizontal: i32,
+    pub depth: i32,
+    pub aim: i32,
+}
+
+impl Location {
+    /// Moves according to `command`, as interpreted by `navigation`.
+    pub fn process_command(mut self, command: &Command, navigation: Navigation) -> Self {
+        match (navigation, command.direction) {
+            (_, Direction::Forward) => {
+                self.horizontal += command.units;
+                self.depth += self.aim * command.units
+            }
+            (Navigation::Plain, Direction::Down) => self.depth += command.units,
+            (Navigation::Plain, Direction::Up) => self.depth -= command.units,
+            (Navigation::Aimed, Direction::Down) => self.aim += command.units,
+            (Navigation::Aimed, Direction::Up) => self.aim -= command.units,
+        }
+        return self;
+    }
+
+    /// The puzzle answer: the horizontal position multiplied by the depth.
+    pub fn answer(&self) -> i64 {
+        return self.horizontal as i64 * self.depth as i64;
+    }
+}
+
+/// The final position after following every command from the surface.
+pub fn navigate(commands: &[Command], navigation: Navigation) -> Location {
+    let mut location = Location::default();
+    for command in commands {
+        location = location.process_command(command, navigation);
+        trace!(
+            "{:?} {} -> {:?}",
+            command.direction,
+            command.units,
+            location
+        );
+    }
+    return location;
+}
+
+/// The position after each of the commands in turn.
+pub fn course(commands: &[Command], navigation: Navigation) -> Vec<Location> {
+    return commands
+        .iter()
+        .scan(Location::default(), |location, command| {
+            *location = location.process_command(command, navigation);
+            Some(*location)
+        })
+        .collect();
+}
+
+fn parse_command(s: &str) -> Result<Command, ParseError> {
+    let (direction, units) = s
+        .split_once(' ')
+        .ok_or_else(|| ParseError::new(s.len() + 1, "expected a direction followed by units"))?;
+    let direction = match direction {
+        "forward" => Direction::Forward,
+        "down" => Direction::Down,
+        "up" => Direction::Up,
+        other => {
+            return Err(ParseError::new(
+                1,
+                format!("expected forward, down or up, found '{}'", other),
+            ))
+        }
+    };
+
+    let units = parse_field(units, s.len() - units.len() + 1, "a number of units")?;
+    Ok(Command { direction, units })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+    #[test]
+    fn part1_example() {
+        let commands = Day2::parse(EXAMPLE).unwrap();
+        let location = navigate(&commands, Navigation::Plain);
+        assert_eq!((location.horizontal, location.depth), (15, 10));
+        assert_eq!(Day2::part1(&commands), 150);
+    }
+
+    #[test]
+    fn part2_example() {
+        let commands = Day2::parse(EXAMPLE).unwrap();
+        let location = navigate(&commands, Navigation::Aimed);
+        assert_eq!((location.horizontal, location.depth), (15, 60));
+        assert_eq!(Day2::part2(&commands), 900);
+    }
+
+    #[test]
+    fn course_example() {
+        let commands = Day2::parse(EXAMPLE).unwrap();
+        let depths: Vec<_> = course(&commands, Navigation::Aimed)
+            .iter()
+            .map(|location| location.depth)
+            .collect();
+        assert_eq!(depths, [0, 0, 40, 40, 40, 60]);
+    }
+}
@@ -0,0 +1,209 @@
+//! Day 20: Trench Map
+
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::input::{sections, Line};
+use crate::solution::{NoAnswer, Solution};
+
+fn parse_pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+/// An image that goes on forever: the pixels in `pixels`, and `background`
+/// everywhere else.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pixels: Grid<bool>,
+    background: bool,
+}
+
+impl Image {
+    fn pixel(&self, x: isize, y: isize) -> bool {
+        if x < 0 || y < 0 {
+            return self.background;
+        }
+        return *self
+            .pixels
+            .get((x as usize, y as usize))
+            .unwrap_or(&self.background);
+    }
+
+    /// Applies the enhancement algorithm once. The image grows by one pixel
+    /// on every side, and the background is enhanced like any other pixel,
+    /// which makes it flicker when the algorithm lights up 9 dark pixels.
+    pub fn enhance(&self, algorithm: &[bool]) -> Image {
+        let (width, height) = (self.pixels.width() + 2, self.pixels.height() + 2);
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                let mut index = 0;
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        index = (index << 1) | self.pixel(x - 1 + dx, y - 1 + dy) as usize;
+                    }
+                }
+                cells.push(algorithm[index]);
+            }
+        }
+        let background = algorithm[if self.background { 511 } else { 0 }];
+        return Image {
+            pixels: Grid::new(width, height, cells),
+            background,
+        };
+    }
+
+    /// How many pixels are lit, or `None` if infinitely many are.
+    pub fn lit(&self) -> Option<usize> {
+        if self.background {
+            return None;
+        }
+        return Some(self.pixels.values().filter(|&&lit| lit).count());
+    }
+}
+
+/// The image enhancement algorithm, and the input image.
+#[derive(Debug, Clone)]
+pub struct Scan {
+    algorithm: Vec<bool>,
+    image: Image,
+}
+
+impl Scan {
+    fn from_sections(sections: Vec<Vec<Line>>) -> Result<Self, ParseError> {
+        let mut sections = sections.into_iter();
+        let mut algorithm = Vec::with_capacity(512);
+        let mut last = None;
+        for line in sections.next().into_iter().flatten() {
+            for (idx, c) in line.text.chars().enumerate() {
+                let pixel = parse_pixel(c)
+                    .ok_or_else(|| line.error(ParseError::new(idx + 1, "expected '#' or '.'")))?;
+                algorithm.push(pixel);
+            }
+            last = Some(line);
+        }
+        if algorithm.len() != 512 {
+            let reason = format!(
+                "expected 512 pixels in the algorithm, found {}",
+                algorithm.len()
+            );
+            let err = ParseError::new(1, reason);
+            return Err(match last {
+                Some(line) => line.error(err),
+                None => err,
+            });
+        }
+
+        let lines = sections.next().unwrap_or_default();
+        let pixels = Grid::from_lines(lines, |text| {
+            text.chars()
+                .enumerate()
+                .map(|(idx, c)| {
+                    parse_pixel(c).ok_or_else(|| ParseError::new(idx + 1, "expected '#' or '.'"))
+                })
+                .collect()
+        })?;
+        let image = Image {
+            pixels,
+            background: false,
+        };
+        return Ok(Scan { algorithm, image });
+    }
+
+    /// The image after enhancing it `times` times.
+    pub fn enhanced(&self, times: usize) -> Image {
+        let mut image = self.image.clone();
+        for _ in 0..times {
+            image = image.enhance(&self.algorithm);
+        }
+        return image;
+    }
+}
+
+const INFINITE: NoAnswer = NoAnswer("infinitely many pixels are lit");
+
+/// Enhances the image from the trench floor.
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Parsed = Scan;
+    type Part1 = Result<usize, NoAnswer>;
+    type Part2 = Result<usize, NoAnswer>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Scan::from_sections(sections(input))
+    }
+
+    fn part1(scan: &Scan) -> Result<usize, NoAnswer> {
+        return scan.enhanced(2).lit().ok_or(INFINITE);
+    }
+
+    fn part2(scan: &Scan) -> Result<usize, NoAnswer> {
+        return scan.enhanced(50).lit().ok_or(INFINITE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+    #[test]
+    fn part1_example() {
+        let scan = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::part1(&scan), Ok(35));
+    }
+
+    #[test]
+    fn part2_example() {
+        let scan = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::part2(&scan), Ok(3351));
+    }
+
+    /// An algorithm that lights a pixel exactly when the pixel in the
+    /// middle of its square was lit, optionally inverting every pixel.
+    fn scan(invert: bool, image: &str) -> Scan {
+        let algorithm: String = (0..512)
+            .map(|idx| {
+                if (idx & 0x10 != 0) != invert {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        return Day20::parse(&format!("{}\n\n{}", algorithm, image)).unwrap();
+    }
+
+    #[test]
+    fn identity_keeps_the_image() {
+        let scan = scan(false, "#..\n.#.\n..#");
+        assert_eq!(Day20::part1(&scan), Ok(3));
+        assert_eq!(Day20::part2(&scan), Ok(3));
+    }
+
+    #[test]
+    fn inversion_flickers_the_background() {
+        let scan = scan(true, "#..\n.#.\n..#");
+        assert_eq!(scan.enhanced(1).lit(), None);
+        // 7 by 7 after two steps, with the original 3 lit pixels
+        assert_eq!(scan.enhanced(2).lit(), Some(3));
+    }
+
+    #[test]
+    fn rejects_short_algorithm() {
+        assert!(Day20::parse("#.#\n\n#").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! Day 21: Dirac Dice
+
+use std::collections::HashMap;
+
+use crate::error::{parse_field, ParseError};
+use crate::input::lines;
+use crate::solution::Solution;
+
+const TRACK_LENGTH: u32 = 10;
+
+fn parse_start(s: &str, player: usize) -> Result<u32, ParseError> {
+    let prefix = format!("Player {} starting position: ", player);
+    let position = s
+        .strip_prefix(prefix.as_str())
+        .ok_or_else(|| ParseError::new(1, format!("expected '{}'", prefix.trim_end())))?;
+    let position: u32 = parse_field(position, prefix.len() + 1, "a position")?;
+    if !(1..=TRACK_LENGTH).contains(&position) {
+        let reason = format!("expected a position from 1 to {}", TRACK_LENGTH);
+        return Err(ParseError::new(prefix.len() + 1, reason));
+    }
+    return Ok(position);
+}
+
+fn advance(position: u32, roll: u32) -> u32 {
+    return (position + roll - 1) % TRACK_LENGTH + 1;
+}
+
+/// Plays with the deterministic die until someone reaches 1000, returning
+/// the losing score times the number of rolls.
+pub fn practice_game(start: [u32; 2]) -> u32 {
+    let (mut positions, mut scores) = (start, [0, 0]);
+    let mut rolls = 0;
+    let mut player = 0;
+    loop {
+        let roll: u32 = (0..3).map(|i| (rolls + i) % 100 + 1).sum();
+        rolls += 3;
+        positions[player] = advance(positions[player], roll);
+        scores[player] += positions[player];
+        if scores[player] >= 1000 {
+            return scores[1 - player] * rolls;
+        }
+        player = 1 - player;
+    }
+}
+
+/// How many universes each player wins in, for the player about to move at
+/// `positions[0]` with `scores[0]`.
+fn dirac_wins(
+    positions: [u32; 2],
+    scores: [u32; 2],
+    memo: &mut HashMap<([u32; 2], [u32; 2]), [u64; 2]>,
+) -> [u64; 2] {
+    // the sum of three rolls of a three sided die, and how many ways to get it
+    const SUMS: [(u32, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+    if let Some(&wins) = memo.get(&(positions, scores)) {
+        return wins;
+    }
+    let mut wins = [0, 0];
+    for (roll, ways) in SUMS {
+        let position = advance(positions[0], roll);
+        let score = scores[0] + position;
+        if score >= 21 {
+            wins[0] += ways;
+        } else {
+            let [other, mine] = dirac_wins([positions[1], position], [scores[1], score], memo);
+            wins[0] += mine * ways;
+            wins[1] += other * ways;
+        }
+    }
+    memo.insert((positions, scores), wins);
+    return wins;
+}
+
+/// Plays Dirac Dice against the submarine's computer.
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Parsed = [u32; 2];
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut start = [0; 2];
+        let mut lines = lines(input);
+        for (idx, position) in start.iter_mut().enumerate() {
+            let line = lines.next().ok_or_else(|| {
+                ParseError::new(
+                    1,
+                    format!("expected player {}'s starting position", idx + 1),
+                )
+            })?;
+            *position = line.parse(|text| parse_start(text, idx + 1))?;
+        }
+        return Ok(start);
+    }
+
+    fn part1(start: &[u32; 2]) -> u32 {
+        return practice_game(*start);
+    }
+
+    fn part2(start: &[u32; 2]) -> u64 {
+        let wins = dirac_wins(*start, [0, 0], &mut HashMap::new());
+        return wins[0].max(wins[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Player 1 starting position: 4
+Player 2 starting position: 8";
+
+    #[test]
+    fn part1_example() {
+        let start = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(Day21::part1(&start), 739785);
+    }
+
+    #[test]
+    fn part2_example() {
+        let start = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(Day21::part2(&start), 444356092776315);
+    }
+
+    #[test]
+    fn rejects_position_off_the_track() {
+        assert!(
+            Day21::parse("Player 1 starting position: 11\nPlayer 2 starting position: 1").is_err()
+        );
+    }
+}
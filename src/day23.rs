@@ -0,0 +1,249 @@
+//! Day 23: Amphipod
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::error::ParseError;
+use crate::input::{sections, Line};
+use crate::solution::{NoAnswer, Solution};
+
+const HALLWAY: usize = 11;
+const ROOMS: usize = 4;
+/// The hallway position outside each room, where amphipods can't stop.
+const DOORS: [usize; ROOMS] = [2, 4, 6, 8];
+const EMPTY: u8 = 0;
+
+/// The rows folded out of the diagram for the second part.
+const UNFOLDED: [[u8; ROOMS]; 2] = [[4, 3, 2, 1], [4, 2, 1, 3]];
+
+/// The amphipods in the side rooms, one row of rooms at a time from the
+/// hallway down, as 1 for Amber up to 4 for Desert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Burrow {
+    rows: Vec<[u8; ROOMS]>,
+}
+
+fn parse_row(line: &Line) -> Result<[u8; ROOMS], ParseError> {
+    let mut row = [EMPTY; ROOMS];
+    for (room, amphipod) in row.iter_mut().enumerate() {
+        let column = 3 + 2 * room;
+        *amphipod = match line.text.as_bytes().get(column) {
+            Some(&c @ b'A'..=b'D') => c - b'A' + 1,
+            _ => {
+                let reason = "expected an amphipod from 'A' to 'D'";
+                return Err(line.error(ParseError::new(column + 1, reason)));
+            }
+        };
+    }
+    return Ok(row);
+}
+
+/// Where an amphipod is in the flattened state: the hallway comes first,
+/// then every room from the top down.
+fn slot(depth: usize, room: usize, level: usize) -> usize {
+    return HALLWAY + room * depth + level;
+}
+
+/// Whether the hallway from `from` to `to` is free, not counting `from`.
+fn hallway_clear(state: &[u8], from: usize, to: usize) -> bool {
+    let (low, high) = if from < to {
+        (from + 1, to)
+    } else {
+        (to, from - 1)
+    };
+    return state[low..=high].iter().all(|&a| a == EMPTY);
+}
+
+fn energy(amphipod: u8) -> usize {
+    return 10usize.pow(amphipod as u32 - 1);
+}
+
+/// Every state reachable in one move, and the energy it takes.
+fn moves(state: &[u8], depth: usize) -> Vec<(Vec<u8>, usize)> {
+    let mut next = Vec::new();
+
+    // into its room, if only amphipods of its own type are left in there
+    for position in 0..HALLWAY {
+        let amphipod = state[position];
+        if amphipod == EMPTY {
+            continue;
+        }
+        let room = amphipod as usize - 1;
+        let door = DOORS[room];
+        let levels = (0..depth).map(|level| state[slot(depth, room, level)]);
+        if levels.clone().any(|a| a != EMPTY && a != amphipod)
+            || !hallway_clear(state, position, door)
+        {
+            continue;
+        }
+        let level = levels.take_while(|&a| a == EMPTY).count() - 1;
+        let mut moved = state.to_vec();
+        moved[position] = EMPTY;
+        moved[slot(depth, room, level)] = amphipod;
+        let steps = position.abs_diff(door) + level + 1;
+        next.push((moved, steps * energy(amphipod)));
+    }
+
+    // out of a room that still holds an amphipod of another type
+    for (room, &door) in DOORS.iter().enumerate() {
+        let level = match (0..depth).find(|&level| state[slot(depth, room, level)] != EMPTY) {
+            Some(level) => level,
+            None => continue,
+        };
+        let settled = (level..depth).all(|l| state[slot(depth, room, l)] as usize == room + 1);
+        if settled {
+            continue;
+        }
+        let amphipod = state[slot(depth, room, level)];
+        for position in (0..HALLWAY).filter(|p| !DOORS.contains(p)) {
+            if !hallway_clear(state, door, position) || state[position] != EMPTY {
+                continue;
+            }
+            let mut moved = state.to_vec();
+            moved[slot(depth, room, level)] = EMPTY;
+            moved[position] = amphipod;
+            let steps = position.abs_diff(door) + level + 1;
+            next.push((moved, steps * energy(amphipod)));
+        }
+    }
+
+    return next;
+}
+
+impl Burrow {
+    fn from_lines(lines: Vec<Line>) -> Result<Self, ParseError> {
+        // the wall, the empty hallway, the rooms and the bottom wall
+        if lines.len() < 4 {
+            return Err(ParseError::new(1, "expected a diagram of the burrow"));
+        }
+        let rows: Vec<[u8; ROOMS]> = lines[2..lines.len() - 1]
+            .iter()
+            .map(parse_row)
+            .collect::<Result<_, _>>()?;
+        // every room has to end up full of its own amphipods
+        for amphipod in 1..=ROOMS as u8 {
+            let count = rows.iter().flatten().filter(|&&a| a == amphipod).count();
+            if count == 0 || count != rows.len() {
+                let reason = format!(
+                    "expected {} of amphipod '{}', found {}",
+                    rows.len(),
+                    (b'A' + amphipod - 1) as char,
+                    count
+                );
+                return Err(ParseError::new(1, reason));
+            }
+        }
+        return Ok(Burrow { rows });
+    }
+
+    /// The burrow with the folded part of the diagram put back in, after the
+    /// first row.
+    pub fn unfolded(&self) -> Burrow {
+        let mut rows = self.rows.clone();
+        rows.splice(1..1, UNFOLDED);
+        return Burrow { rows };
+    }
+
+    /// The least energy it takes to sort every amphipod into its room, or
+    /// `None` if they can't be.
+    pub fn least_energy(&self) -> Option<usize> {
+        let depth = self.rows.len();
+        let mut start = vec![EMPTY; HALLWAY + ROOMS * depth];
+        let mut goal = start.clone();
+        for (level, row) in self.rows.iter().enumerate() {
+            for room in 0..ROOMS {
+                start[slot(depth, room, level)] = row[room];
+                goal[slot(depth, room, level)] = room as u8 + 1;
+            }
+        }
+
+        let mut best: HashMap<Vec<u8>, usize> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        best.insert(start.clone(), 0);
+        queue.push(Reverse((0, start)));
+        while let Some(Reverse((cost, state))) = queue.pop() {
+            if state == goal {
+                return Some(cost);
+            }
+            if cost > best[&state] {
+                continue;
+            }
+            for (next, step_cost) in moves(&state, depth) {
+                let next_cost = cost + step_cost;
+                if best.get(&next).is_none_or(|&known| next_cost < known) {
+                    best.insert(next.clone(), next_cost);
+                    queue.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+        return None;
+    }
+}
+
+const UNSORTABLE: NoAnswer = NoAnswer("the amphipods can't all be sorted");
+
+/// Sorts the amphipods into their rooms.
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Parsed = Burrow;
+    type Part1 = Result<usize, NoAnswer>;
+    type Part2 = Result<usize, NoAnswer>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let diagram = sections(input).into_iter().next();
+        Burrow::from_lines(diagram.unwrap_or_default())
+    }
+
+    fn part1(burrow: &Burrow) -> Result<usize, NoAnswer> {
+        return burrow.least_energy().ok_or(UNSORTABLE);
+    }
+
+    fn part2(burrow: &Burrow) -> Result<usize, NoAnswer> {
+        return burrow.unfolded().least_energy().ok_or(UNSORTABLE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+    #[test]
+    fn parses_rooms() {
+        let burrow = Day23::parse(EXAMPLE).unwrap();
+        assert_eq!(burrow.rows, vec![[2, 3, 2, 4], [1, 4, 3, 1]]);
+    }
+
+    #[test]
+    fn rejects_wrong_amphipod_counts() {
+        let full = EXAMPLE.replace(['B', 'C', 'D'], "A");
+        let err = Day23::parse(&full).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(1, "expected 2 of amphipod 'A', found 8")
+        );
+        assert!(Day23::parse(&EXAMPLE.replacen('D', "A", 1)).is_err());
+    }
+
+    #[test]
+    fn part1_example() {
+        let burrow = Day23::parse(EXAMPLE).unwrap();
+        assert_eq!(Day23::part1(&burrow), Ok(12521));
+    }
+
+    #[test]
+    fn part2_example() {
+        let burrow = Day23::parse(EXAMPLE).unwrap();
+        assert_eq!(Day23::part2(&burrow), Ok(44169));
+    }
+}
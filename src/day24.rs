@@ -0,0 +1,281 @@
+//! Day 24: Arithmetic Logic Unit
+
+use crate::error::{parse_field, ParseError};
+use crate::input::lines;
+use crate::solution::{NoAnswer, Solution};
+use crate::trace;
+
+const REGISTERS: &str = "wxyz";
+const W: usize = 0;
+const X: usize = 1;
+const Y: usize = 2;
+const Z: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(usize),
+    Number(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Inp(usize),
+    Add(usize, Operand),
+    Mul(usize, Operand),
+    Div(usize, Operand),
+    Mod(usize, Operand),
+    Eql(usize, Operand),
+}
+
+fn parse_register(s: &str, column: usize) -> Result<usize, ParseError> {
+    match REGISTERS.find(s) {
+        Some(register) if s.len() == 1 => Ok(register),
+        _ => Err(ParseError::new(
+            column,
+            "expected a register from 'w' to 'z'",
+        )),
+    }
+}
+
+fn parse_instruction(s: &str) -> Result<Instruction, ParseError> {
+    let fields: Vec<&str> = s.split(' ').collect();
+    let a_column = fields[0].len() + 2;
+    let a = match fields.get(1) {
+        Some(a) => parse_register(a, a_column)?,
+        None => return Err(ParseError::new(s.len() + 1, "expected a register")),
+    };
+    if fields[0] == "inp" {
+        if fields.len() > 2 {
+            return Err(ParseError::new(
+                a_column + 2,
+                "expected nothing after the register",
+            ));
+        }
+        return Ok(Instruction::Inp(a));
+    }
+
+    let b_column = a_column + 2;
+    let b = match fields.get(2) {
+        Some(b) if b.parse::<i64>().is_ok() => {
+            Operand::Number(parse_field(b, b_column, "a number")?)
+        }
+        Some(b) => Operand::Register(parse_register(b, b_column)?),
+        None => {
+            return Err(ParseError::new(
+                s.len() + 1,
+                "expected a register or a number",
+            ))
+        }
+    };
+    if fields.len() > 3 {
+        return Err(ParseError::new(
+            b_column + fields[2].len() + 1,
+            "expected two operands",
+        ));
+    }
+    match fields[0] {
+        "add" => Ok(Instruction::Add(a, b)),
+        "mul" => Ok(Instruction::Mul(a, b)),
+        "div" => Ok(Instruction::Div(a, b)),
+        "mod" => Ok(Instruction::Mod(a, b)),
+        "eql" => Ok(Instruction::Eql(a, b)),
+        _ => Err(ParseError::new(
+            1,
+            "expected inp, add, mul, div, mod or eql",
+        )),
+    }
+}
+
+/// Runs `program` reading `input` in order, returning the registers at the
+/// end, or `None` if it runs out of input or divides by zero.
+pub fn run(program: &[Instruction], input: &[i64]) -> Option<[i64; 4]> {
+    let mut registers = [0; 4];
+    let mut input = input.iter();
+    for instruction in program {
+        let value = |operand: &Operand, registers: &[i64; 4]| match *operand {
+            Operand::Register(register) => registers[register],
+            Operand::Number(number) => number,
+        };
+        match instruction {
+            Instruction::Inp(a) => registers[*a] = *input.next()?,
+            Instruction::Add(a, b) => registers[*a] += value(b, &registers),
+            Instruction::Mul(a, b) => registers[*a] *= value(b, &registers),
+            Instruction::Div(a, b) => {
+                registers[*a] = registers[*a].checked_div(value(b, &registers))?
+            }
+            Instruction::Mod(a, b) => {
+                let b = value(b, &registers);
+                if registers[*a] < 0 || b <= 0 {
+                    return None;
+                }
+                registers[*a] %= b;
+            }
+            Instruction::Eql(a, b) => {
+                registers[*a] = (registers[*a] == value(b, &registers)) as i64
+            }
+        }
+    }
+    return Some(registers);
+}
+
+/// How long each of MONAD's blocks is, one for each digit of the model number.
+const BLOCK_LENGTH: usize = 18;
+const DIGITS: usize = 14;
+
+/// The three numbers that differ between MONAD's blocks: what `z` is divided
+/// by, what is added to `x`, and what is added to `y` before it is pushed.
+fn block_parameters(program: &[Instruction]) -> Option<Vec<(i64, i64, i64)>> {
+    if program.len() != BLOCK_LENGTH * DIGITS {
+        return None;
+    }
+    return program
+        .chunks(BLOCK_LENGTH)
+        .map(|block| match (block[0], block[4], block[5], block[15]) {
+            (
+                Instruction::Inp(W),
+                Instruction::Div(Z, Operand::Number(div)),
+                Instruction::Add(X, Operand::Number(check)),
+                Instruction::Add(Y, Operand::Number(offset)),
+            ) => Some((div, check, offset)),
+            _ => None,
+        })
+        .collect();
+}
+
+/// The largest or smallest model number MONAD accepts. MONAD keeps a stack
+/// of base 26 digits in `z`: blocks dividing by 1 push their digit plus an
+/// offset, and blocks dividing by 26 pop it again, which only leaves `z` at
+/// zero if their own digit equals the popped one plus their check. Each such
+/// pair of digits is then as large (or small) as that difference allows.
+pub fn model_number(program: &[Instruction], largest: bool) -> Option<u64> {
+    let parameters = block_parameters(program)?;
+    let mut digits = [0i64; DIGITS];
+    let mut stack = Vec::new();
+    for (idx, &(div, check, offset)) in parameters.iter().enumerate() {
+        if div == 1 {
+            stack.push((idx, offset));
+            continue;
+        }
+        let (pushed, pushed_offset) = stack.pop()?;
+        let delta = pushed_offset + check;
+        trace!("digit {} = digit {} + {}", idx, pushed, delta);
+        let pushed_digit = if largest {
+            9.min(9 - delta)
+        } else {
+            1.max(1 - delta)
+        };
+        digits[pushed] = pushed_digit;
+        digits[idx] = pushed_digit + delta;
+    }
+    if !stack.is_empty() || digits.iter().any(|d| !(1..=9).contains(d)) {
+        return None;
+    }
+
+    let registers = run(program, &digits)?;
+    if registers[Z] != 0 {
+        return None;
+    }
+    return Some(digits.iter().fold(0, |acc, &d| acc * 10 + d as u64));
+}
+
+const NOT_MONAD: NoAnswer = NoAnswer("the program is not a MONAD that accepts a model number");
+
+/// Finds the model numbers the submarine's MONAD program accepts.
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Parsed = Vec<Instruction>;
+    type Part1 = Result<u64, NoAnswer>;
+    type Part2 = Result<u64, NoAnswer>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        lines(input)
+            .map(|line| line.parse(parse_instruction))
+            .collect()
+    }
+
+    fn part1(program: &Vec<Instruction>) -> Result<u64, NoAnswer> {
+        return model_number(program, true).ok_or(NOT_MONAD);
+    }
+
+    fn part2(program: &Vec<Instruction>) -> Result<u64, NoAnswer> {
+        return model_number(program, false).ok_or(NOT_MONAD);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BINARY: &str = "inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2";
+
+    /// A program shaped like MONAD, with made up parameters for each block.
+    fn monad() -> String {
+        const PARAMETERS: [(i64, i64, i64); DIGITS] = [
+            (1, 11, 6),
+            (1, 12, 12),
+            (26, -14, 0),
+            (1, 13, 1),
+            (26, 4, 0),
+            (26, -6, 0),
+            (1, 10, 3),
+            (1, 14, 9),
+            (26, -9, 0),
+            (1, 11, 15),
+            (26, -8, 0),
+            (26, -10, 0),
+            (1, 12, 2),
+            (26, 5, 0),
+        ];
+        return PARAMETERS
+            .iter()
+            .map(|(div, check, offset)| {
+                format!(
+                    "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\n\
+                     mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\n\
+                     add y {}\nmul y x\nadd z y",
+                    div, check, offset
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    #[test]
+    fn runs_binary_conversion() {
+        let program = Day24::parse(BINARY).unwrap();
+        assert_eq!(run(&program, &[13]), Some([1, 1, 0, 1]));
+        assert_eq!(run(&program, &[]), None);
+    }
+
+    #[test]
+    fn finds_model_numbers() {
+        let program = Day24::parse(&monad()).unwrap();
+        assert_eq!(Day24::part1(&program), Ok(99749999929229));
+        assert_eq!(Day24::part2(&program), Ok(13116181118118));
+    }
+
+    #[test]
+    fn other_programs_have_no_model_number() {
+        let program = Day24::parse(BINARY).unwrap();
+        assert_eq!(Day24::part1(&program), Err(NOT_MONAD));
+    }
+
+    #[test]
+    fn rejects_unknown_instruction() {
+        assert!(Day24::parse("jmp x 2").is_err());
+        assert!(Day24::parse("add v 2").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! Day 25: Sea Cucumber
+
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::{Solution, Unsolved};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+/// The sea floor, which wraps around at both edges.
+#[derive(Debug, Clone)]
+pub struct SeaFloor {
+    cells: Grid<Cell>,
+}
+
+impl SeaFloor {
+    /// Moves every sea cucumber of `herd` that can, all at once. Returns
+    /// whether any of them moved.
+    fn move_herd(&mut self, herd: Cell) -> bool {
+        let (width, height) = (self.cells.width(), self.cells.height());
+        let moving: Vec<_> = self
+            .cells
+            .iter()
+            .filter(|(_, &cell)| cell == herd)
+            .map(|((x, y), _)| {
+                let next = match herd {
+                    Cell::East => ((x + 1) % width, y),
+                    _ => (x, (y + 1) % height),
+                };
+                ((x, y), next)
+            })
+            .filter(|(_, next)| self.cells[*next] == Cell::Empty)
+            .collect();
+        for &(from, to) in &moving {
+            self.cells[from] = Cell::Empty;
+            self.cells[to] = herd;
+        }
+        return !moving.is_empty();
+    }
+
+    /// The first step on which no sea cucumber moves.
+    pub fn steps_until_stuck(&self) -> usize {
+        let mut floor = self.clone();
+        let mut steps = 1;
+        // both herds have to try, even if the east-facing one is stuck
+        while floor.move_herd(Cell::East) | floor.move_herd(Cell::South) {
+            steps += 1;
+        }
+        return steps;
+    }
+}
+
+/// Waits for the sea cucumbers to stop moving so the submarine can land.
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Parsed = SeaFloor;
+    type Part1 = usize;
+    type Part2 = Unsolved;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let cells = Grid::parse_chars(input, "'>', 'v' or '.'", |c| match c {
+            '.' => Some(Cell::Empty),
+            '>' => Some(Cell::East),
+            'v' => Some(Cell::South),
+            _ => None,
+        })?;
+        return Ok(SeaFloor { cells });
+    }
+
+    fn part1(floor: &SeaFloor) -> usize {
+        return floor.steps_until_stuck();
+    }
+
+    fn part2(_: &SeaFloor) -> Unsolved {
+        Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+    #[test]
+    fn part1_example() {
+        let floor = Day25::parse(EXAMPLE).unwrap();
+        assert_eq!(Day25::part1(&floor), 58);
+    }
+
+    #[test]
+    fn east_moves_before_south() {
+        let mut floor = Day25::parse("..>..\n..v..").unwrap();
+        assert!(floor.move_herd(Cell::East));
+        assert!(floor.move_herd(Cell::South));
+        assert_eq!(
+            floor.cells.row(0),
+            [
+                Cell::Empty,
+                Cell::Empty,
+                Cell::South,
+                Cell::East,
+                Cell::Empty
+            ]
+        );
+    }
+}
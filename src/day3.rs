@@ -1,23 +1,17 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-    path::Path,
-};
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+//! Day 3: Binary Diagnostic
+
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
+use crate::trace;
 
 enum BitCriteria {
     MostCommon,
     LeastCommon,
 }
 
-struct DiagnosticReport {
+/// The binary numbers of the diagnostic report, one bit per entry.
+pub struct DiagnosticReport {
     binary_numbers: Vec<Vec<u8>>,
 }
 
@@ -54,21 +48,23 @@ impl DiagnosticReport {
         }
     }
 
-    fn gamma(&self) -> usize {
+    /// The gamma rate: the most common bit in each position.
+    pub fn gamma(&self) -> usize {
         let most_common_bits = (0..self.num_bits())
             .map(|bit_index| self.most_common_bit(bit_index))
             .collect::<Vec<_>>();
         return bit_vector_to_number(&most_common_bits);
     }
 
-    fn epsilon(&self) -> usize {
+    /// The epsilon rate: the least common bit in each position.
+    pub fn epsilon(&self) -> usize {
         let least_common_bits = (0..self.num_bits())
             .map(|bit_index| self.least_common_bit(bit_index))
             .collect::<Vec<_>>();
         return bit_vector_to_number(&least_common_bits);
     }
 
-    /**
+    /*
          * Next, you should verify the life support rating, which can be determined by multiplying the oxygen generator rating by the CO2 scrubber rating.
 
     Both the oxygen generator rating and the CO2 scrubber rating are values that can be found in your diagnostic report - finding them is the tricky part.
@@ -105,7 +101,8 @@ impl DiagnosticReport {
         }
     }
 
-    fn co2_scrubber_rating(&self) -> Vec<u8> {
+    /// The bits of the CO2 scrubber rating.
+    pub fn co2_scrubber_rating(&self) -> Vec<u8> {
         let mut filtered_report = DiagnosticReport {
             binary_numbers: self.binary_numbers.to_owned(),
         };
@@ -113,14 +110,15 @@ impl DiagnosticReport {
             filtered_report =
                 filtered_report.filter_bit_criteria(bit_index, BitCriteria::LeastCommon);
             if filtered_report.binary_numbers.len() == 1 {
-                println!("found 1");
+                trace!("found 1");
                 break;
             }
         }
         return filtered_report.binary_numbers[0].to_owned();
     }
 
-    fn oxygen_generator_rating(&self) -> Vec<u8> {
+    /// The bits of the oxygen generator rating.
+    pub fn oxygen_generator_rating(&self) -> Vec<u8> {
         let mut filtered_report = DiagnosticReport {
             binary_numbers: self.binary_numbers.to_owned(),
         };
@@ -128,51 +126,90 @@ impl DiagnosticReport {
             filtered_report =
                 filtered_report.filter_bit_criteria(bit_index, BitCriteria::MostCommon);
             if filtered_report.binary_numbers.len() == 1 {
-                println!("found 1");
+                trace!("found 1");
                 break;
             }
-            println! {"filtered down to {}", filtered_report.binary_numbers.len()};
+            trace!("filtered down to {}", filtered_report.binary_numbers.len());
         }
         return filtered_report.binary_numbers[0].to_owned();
     }
 }
 
-pub(crate) fn day3part2() -> (usize, usize) {
-    let lines = read_lines("input3.t").unwrap();
-    let binary_numbers: Vec<_> = lines.map(|l| parse_status_bits(l.unwrap())).collect();
-    let diagnostics = DiagnosticReport { binary_numbers };
-    return (
-        bit_vector_to_number(&diagnostics.oxygen_generator_rating()),
-        bit_vector_to_number(&diagnostics.co2_scrubber_rating()),
-    );
-}
-
 fn filter_by_bit(report: &DiagnosticReport, bit_index: usize, bit_value: u8) -> DiagnosticReport {
-    println!("starting with {}", report.binary_numbers.len());
+    trace!("starting with {}", report.binary_numbers.len());
     let filtered_values = report
         .binary_numbers
         .iter()
         .filter(|binary_number| binary_number[bit_index] == bit_value)
         .map(|x| x.to_owned())
         .collect::<Vec<Vec<u8>>>();
-    println!("filtered to {}", filtered_values.len());
+    trace!("filtered to {}", filtered_values.len());
     return DiagnosticReport {
         binary_numbers: filtered_values,
     };
 }
 
-pub(crate) fn day3part1() -> (usize, usize) {
-    let lines = read_lines("input3.t").unwrap();
-    let binary_numbers: Vec<_> = lines.map(|l| parse_status_bits(l.unwrap())).collect();
-    let diagnostics = DiagnosticReport { binary_numbers };
-    return (diagnostics.gamma(), diagnostics.epsilon());
+/// Decodes the power and life support ratings from a diagnostic report.
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Parsed = DiagnosticReport;
+    type Part1 = (usize, usize);
+    type Part2 = (usize, usize);
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut binary_numbers: Vec<Vec<u8>> = Vec::new();
+        for line in lines(input) {
+            let bits = line.parse(parse_status_bits)?;
+            if bits.is_empty() || bits.len() > usize::BITS as usize {
+                let reason = format!(
+                    "expected a number of 1 to {} bits, found {}",
+                    usize::BITS,
+                    bits.len()
+                );
+                return Err(line.error(ParseError::new(1, reason)));
+            }
+            // every bit position is compared across all the numbers
+            if let Some(first) = binary_numbers.first() {
+                if bits.len() != first.len() {
+                    let reason = format!("expected {} bits, found {}", first.len(), bits.len());
+                    return Err(line.error(ParseError::new(1, reason)));
+                }
+            }
+            binary_numbers.push(bits);
+        }
+        if binary_numbers.is_empty() {
+            return Err(ParseError::new(1, "the diagnostic report is empty"));
+        }
+        Ok(DiagnosticReport { binary_numbers })
+    }
+
+    fn part1(diagnostics: &DiagnosticReport) -> (usize, usize) {
+        return (diagnostics.gamma(), diagnostics.epsilon());
+    }
+
+    fn part2(diagnostics: &DiagnosticReport) -> (usize, usize) {
+        return (
+            bit_vector_to_number(&diagnostics.oxygen_generator_rating()),
+            bit_vector_to_number(&diagnostics.co2_scrubber_rating()),
+        );
+    }
 }
 
-fn parse_status_bits(s: String) -> Vec<u8> {
-    s.chars().map(|c| c.to_digit(2).unwrap() as u8).collect()
+fn parse_status_bits(s: &str) -> Result<Vec<u8>, ParseError> {
+    s.chars()
+        .enumerate()
+        .map(|(idx, c)| {
+            c.to_digit(2)
+                .map(|bit| bit as u8)
+                .ok_or_else(|| ParseError::new(idx + 1, format!("expected 0 or 1, found '{}'", c)))
+        })
+        .collect()
 }
 
-fn bit_vector_to_number(bit_vector: &Vec<u8>) -> usize {
+fn bit_vector_to_number(bit_vector: &[u8]) -> usize {
     let string_bits = bit_vector
         .iter()
         .map(|bit| bit.to_string())
@@ -180,3 +217,47 @@ fn bit_vector_to_number(bit_vector: &Vec<u8>) -> usize {
     let val = usize::from_str_radix(&string_bits, 2).unwrap();
     return val;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+    #[test]
+    fn part1_example() {
+        let report = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part1(&report), (22, 9));
+    }
+
+    #[test]
+    fn part2_example() {
+        let report = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part2(&report), (23, 10));
+    }
+
+    #[test]
+    fn rejects_empty_and_overlong_numbers() {
+        assert!(Day3::parse("\n").is_err());
+        assert!(Day3::parse(&"1".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn rejects_numbers_of_different_widths() {
+        let err = Day3::parse("101\n1").err().unwrap();
+        assert!(err
+            .to_string()
+            .starts_with("line 2, column 1: expected 3 bits, found 1"));
+    }
+}
@@ -1,59 +1,56 @@
-use std::{
-    fs::File,
-    io::{self, BufRead, BufReader, Lines, Result},
-    path::Path,
-};
-
-fn read_lines<P>(filename: P) -> Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+//! Day 4: Giant Squid
 
-pub(crate) fn day4part1() -> (usize, usize) {
-    let mut lines = read_lines("input4.t").unwrap();
-    let mut game = BingoGame::from_lines(&mut lines);
-    game.play()
-}
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::input::{sections, separated, Line};
+use crate::solution::Solution;
+use crate::trace;
 
-pub(crate) fn day4part2() -> (usize, usize) {
-    let mut lines = read_lines("input4.t").unwrap();
-    let mut game = BingoGame::from_lines(&mut lines);
-    game.play_to_lose()
-}
+/// Plays bingo against the squid, to win first and to lose last.
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Parsed = BingoGame;
+    type Part1 = (usize, usize);
+    type Part2 = (usize, usize);
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        BingoGame::from_sections(sections(input))
+    }
 
-const BOARD_SIZE: usize = 5;
+    fn part1(game: &BingoGame) -> (usize, usize) {
+        game.clone().play()
+    }
+
+    fn part2(game: &BingoGame) -> (usize, usize) {
+        game.clone().play_to_lose()
+    }
+}
 
+#[derive(Clone)]
 struct BingoCard {
-    board: Vec<(u8, bool)>,
+    board: Grid<(u8, bool)>,
 }
 
-struct BingoGame {
+/// The numbers drawn, in order, and the cards playing along.
+#[derive(Clone)]
+pub struct BingoGame {
     boards: Vec<BingoCard>,
     numbers: Vec<u8>,
 }
 
 impl BingoCard {
-    fn from_lines(lines: &mut Lines<BufReader<File>>) -> Option<Self> {
-        // skip the empt line
-        lines.next();
-        let mut numbers = Vec::<u8>::new();
-        for _ in 0..BOARD_SIZE {
-            if let Some(line) = lines.next() {
-                numbers.extend(parse_numbers(line.unwrap()));
-            } else {
-                return None;
-            }
-        }
-        let board: Vec<(u8, bool)> = numbers.iter().map(|num| (*num, false)).collect();
-        println!("created a board with {} numbers", board.len());
-        return Some(BingoCard { board });
+    fn from_lines(lines: &[Line]) -> Result<Self, ParseError> {
+        let numbers = Grid::from_lines(lines.iter().copied(), parse_numbers)?;
+        let board = numbers.map(|num| (*num, false));
+        trace!("created a {}x{} board", board.width(), board.height());
+        return Ok(BingoCard { board });
     }
 
     fn mark(&mut self, number: u8) {
-        if let Some(item) = self.board.iter_mut().find(|(value, _)| *value == number) {
+        if let Some(item) = self.board.values_mut().find(|(value, _)| *value == number) {
             item.1 = true;
         }
     }
@@ -61,56 +58,51 @@ impl BingoCard {
     fn score(&self) -> usize {
         return self
             .board
-            .iter()
-            .filter(|(val, mark)| *mark == false)
-            .map(|(val, mark)| *val as usize)
+            .values()
+            .filter(|(_, mark)| !*mark)
+            .map(|(val, _)| *val as usize)
             .sum();
     }
 
     fn has_won(&self) -> bool {
-        // check rows
-        for row in 0..BOARD_SIZE {
-            let mut all_cols = true;
-            for col in 0..BOARD_SIZE {
-                let idx = row * BOARD_SIZE + col;
-                all_cols = all_cols && self.board[idx].1 == true;
-            }
-
-            if all_cols == true {
-                return true;
-            }
-        }
-        // check columns
-        for col in 0..BOARD_SIZE {
-            let mut all_rows = true;
-            for row in 0..BOARD_SIZE {
-                let idx = row * BOARD_SIZE + col;
-                all_rows = all_rows && self.board[idx].1 == true;
-            }
-            if all_rows == true {
-                return true;
-            }
-        }
-        return false;
+        let mut rows = self.board.rows();
+        let mut columns = self.board.columns();
+        return rows.any(|row| row.iter().all(|(_, mark)| *mark))
+            || columns.any(|mut column| column.all(|(_, mark)| *mark));
     }
 }
 
 impl BingoGame {
-    fn from_lines(lines: &mut Lines<BufReader<File>>) -> Self {
+    fn from_sections(sections: Vec<Vec<Line>>) -> Result<Self, ParseError> {
+        let mut sections = sections.into_iter();
         // read number input
-        let input_line = lines.next().unwrap().unwrap();
+        let numbers = match sections.next() {
+            Some(lines) if lines.len() == 1 => lines[0].parse(parse_numbers)?,
+            Some(lines) => {
+                let reason = "expected a blank line after the drawn numbers";
+                return Err(lines[1].error(ParseError::new(1, reason)));
+            }
+            None => {
+                return Err(ParseError::new(
+                    1,
+                    "expected the drawn numbers, found nothing",
+                ))
+            }
+        };
         let mut game = BingoGame {
             boards: Vec::<BingoCard>::new(),
-            numbers: parse_numbers(input_line),
+            numbers,
         };
-        while let Some(board) = BingoCard::from_lines(lines) {
-            game.boards.push(board);
+        for lines in sections {
+            game.boards.push(BingoCard::from_lines(&lines)?);
         }
 
-        return game;
+        return Ok(game);
     }
 
-    fn play(&mut self) -> (usize, usize) {
+    /// Draws numbers until a card wins, returning the last number drawn and
+    /// the sum of the card's unmarked numbers.
+    pub fn play(&mut self) -> (usize, usize) {
         for num in &self.numbers {
             for board in &mut self.boards {
                 board.mark(*num);
@@ -123,7 +115,8 @@ impl BingoGame {
         return (0, 0);
     }
 
-    fn play_to_lose(&mut self) -> (usize, usize) {
+    /// Like [`BingoGame::play`], but for the card that wins last.
+    pub fn play_to_lose(&mut self) -> (usize, usize) {
         for num in &self.numbers {
             for board in &mut self.boards {
                 board.mark(*num);
@@ -138,11 +131,44 @@ impl BingoGame {
     }
 }
 
-fn parse_numbers(s: String) -> Vec<u8> {
-    println!("{}", s);
-    return s
-        .split(&[',', ' '][..])
-        .filter(|n| *n != "")
-        .map(|n| n.parse().unwrap())
-        .collect();
+fn parse_numbers(s: &str) -> Result<Vec<u8>, ParseError> {
+    trace!("{}", s);
+    return separated(s, &[',', ' '], "a number");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+    #[test]
+    fn part1_example() {
+        let game = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part1(&game), (24, 188));
+    }
+
+    #[test]
+    fn part2_example() {
+        let game = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part2(&game), (13, 148));
+    }
 }
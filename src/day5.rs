@@ -1,17 +1,10 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{self, BufRead, BufReader, Lines, Result},
-    path::Path,
-};
-
-fn read_lines<P>(filename: P) -> Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+//! Day 5: Hydrothermal Venture
+
+use std::collections::HashMap;
+
+use crate::error::ParseError;
+use crate::input::{lines, separated};
+use crate::solution::Solution;
 
 struct VentLine {
     start: (isize, isize),
@@ -19,16 +12,31 @@ struct VentLine {
 }
 
 impl VentLine {
-    fn from_string(s: &str) -> Option<Self> {
-        let x = parse_numbers(s);
+    fn from_string(s: &str) -> Result<Self, ParseError> {
+        let x = parse_numbers(s)?;
+        if x.len() != 4 {
+            let reason = format!("expected 'x1,y1 -> x2,y2', found {} numbers", x.len());
+            return Err(ParseError::new(1, reason));
+        }
         let start = (x[0], x[1]);
         let end = (x[2], x[3]);
-        Some(VentLine { start, end })
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        if dx != 0 && dy != 0 && dx.abs() != dy.abs() {
+            return Err(ParseError::new(
+                1,
+                "vent lines must be horizontal, vertical or at 45 degrees",
+            ));
+        }
+        Ok(VentLine { start, end })
+    }
+
+    fn is_diagonal(&self) -> bool {
+        return self.start.0 != self.end.0 && self.start.1 != self.end.1;
     }
 
     fn step(&self) -> (isize, isize) {
-        let x_step = ((self.end.0 - self.start.0) as isize).signum();
-        let y_step = ((self.end.1 - self.start.1) as isize).signum();
+        let x_step = (self.end.0 - self.start.0).signum();
+        let y_step = (self.end.1 - self.start.1).signum();
         return (x_step, y_step);
     }
 }
@@ -76,24 +84,27 @@ impl<'a> IntoIterator for &'a VentLine {
     }
 }
 
-struct AllVents {
+/// Every line of hydrothermal vents on the ocean floor.
+pub struct AllVents {
     vents: Vec<VentLine>,
 }
 
 impl AllVents {
-    fn parse_vent_lines(mut lines: Lines<BufReader<File>>) -> Self {
-        let mut vents = Vec::new();
-        while let Some(input_line) = lines.next() {
-            if let Some(vent_line) = VentLine::from_string(&input_line.unwrap()) {
-                vents.push(vent_line);
-            }
-        }
-        return Self { vents };
+    fn parse_vent_lines(input: &str) -> Result<Self, ParseError> {
+        let vents = lines(input)
+            .map(|line| line.parse(VentLine::from_string))
+            .collect::<Result<_, _>>()?;
+        return Ok(Self { vents });
     }
 
-    fn all_points_count(&self) -> HashMap<(isize, isize), i32> {
+    /// How many of the vent lines allowed by `policy` cover each point.
+    pub fn all_points_count(&self, policy: LinePolicy) -> HashMap<(isize, isize), i32> {
         let mut all_points = HashMap::new();
-        for vent in &self.vents {
+        let vents = self
+            .vents
+            .iter()
+            .filter(|vent| policy == LinePolicy::WithDiagonals || !vent.is_diagonal());
+        for vent in vents {
             for point in vent {
                 let e = all_points.entry(point).or_insert(0);
                 *e += 1;
@@ -104,18 +115,73 @@ impl AllVents {
     }
 }
 
-pub(crate) fn day5part1() -> usize {
-    let lines = read_lines("input5.t").unwrap();
-    let vents = AllVents::parse_vent_lines(lines);
-    let point_counts = vents.all_points_count();
+/// Which vent lines to consider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinePolicy {
+    /// Only horizontal and vertical lines (part 1).
+    Orthogonal,
+    /// Diagonal lines at 45 degrees too (part 2).
+    WithDiagonals,
+}
+
+/// Points covered by more than one of the vent lines allowed by `policy`.
+pub fn overlaps(vents: &AllVents, policy: LinePolicy) -> usize {
+    let point_counts = vents.all_points_count(policy);
 
     return point_counts.iter().filter(|&(&_, &v)| v > 1).count();
 }
 
-fn parse_numbers(s: &str) -> Vec<isize> {
-    return s
-        .split(&[',', '-', '>', ' '][..])
-        .filter(|n| *n != "")
-        .map(|n| n.parse().unwrap())
-        .collect();
+/// Counts the points where hydrothermal vent lines overlap.
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Parsed = AllVents;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        AllVents::parse_vent_lines(input)
+    }
+
+    fn part1(vents: &AllVents) -> usize {
+        return overlaps(vents, LinePolicy::Orthogonal);
+    }
+
+    fn part2(vents: &AllVents) -> usize {
+        return overlaps(vents, LinePolicy::WithDiagonals);
+    }
+}
+
+fn parse_numbers(s: &str) -> Result<Vec<isize>, ParseError> {
+    return separated(s, &[',', '-', '>', ' '], "a coordinate");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    #[test]
+    fn part1_example() {
+        let vents = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part1(&vents), 5);
+    }
+
+    #[test]
+    fn part2_example() {
+        let vents = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part2(&vents), 12);
+    }
 }
@@ -1,91 +1,359 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader, Lines, Result},
-    path::Path,
-};
-
-fn parse_numbers(s: &str) -> Vec<i32> {
-    return s
-        .split(&[','][..])
-        .filter(|n| *n != "")
-        .map(|n| n.parse().unwrap())
-        .collect();
+//! Day 6: Lanternfish
+
+use std::fmt::Debug;
+
+use crate::bigint::BigUint;
+use crate::debug;
+use crate::error::ParseError;
+use crate::input::{lines, separated};
+use crate::solution::Solution;
+
+/// When fish give birth, and what their timers are set to afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lifecycle {
+    /// The timer of a fish that has just given birth.
+    pub reset: usize,
+    /// The timer of a fish that has just been born.
+    pub newborn: usize,
 }
 
-fn read_lines<P>(filename: P) -> Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
+impl Lifecycle {
+    /// The puzzle's lanternfish, which give birth every 7 days, and whose
+    /// newborns take two more days for their first cycle.
+    pub const LANTERNFISH: Lifecycle = Lifecycle {
+        reset: 6,
+        newborn: 8,
+    };
+
+    /// How many different timer values a fish can have, starting at 0.
+    pub fn timers(&self) -> usize {
+        self.reset.max(self.newborn) + 1
+    }
 }
 
-struct LanternFish {
-    state: i32,
+/// The arithmetic that populations are counted in.
+pub trait Counting {
+    type Count: Clone + Debug;
+
+    fn count(&self, n: u64) -> Self::Count;
+    fn add(&self, a: &Self::Count, b: &Self::Count) -> Self::Count;
+    fn mul(&self, a: &Self::Count, b: &Self::Count) -> Self::Count;
 }
 
-impl LanternFish {
-    fn next_state(&self) -> Vec<LanternFish> {
-        match self.state {
-            0 => vec![LanternFish { state: 6 }, LanternFish { state: 8 }],
-            _ => vec![LanternFish {
-                state: self.state - 1,
-            }],
-        }
+/// Exact counts, however large they get.
+pub struct Exact;
+
+impl Counting for Exact {
+    type Count = BigUint;
+
+    fn count(&self, n: u64) -> BigUint {
+        BigUint::from(n)
     }
-}
 
-fn next_day(school: Vec<LanternFish>) -> Vec<LanternFish> {
-    school
-        .iter()
-        .map(|fish| fish.next_state())
-        .flat_map(|s| s)
-        .collect()
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a.add(b)
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a.mul(b)
+    }
 }
 
-fn by_day(count: i32) -> HashMap<i32, usize> {
-    let mut res = HashMap::new();
+/// Counts modulo a number (which must not be 0), for populations too large
+/// to write down.
+pub struct Modulo(pub u64);
 
-    // initial seven days, based on the initial state
-    for day in 0..7i32 {
-        res.insert(day, 1);
+impl Counting for Modulo {
+    type Count = u64;
+
+    fn count(&self, n: u64) -> u64 {
+        n % self.0
     }
-    for day in 7..9 {
-        res.insert(day, 2);
+
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 + *b as u128) % self.0 as u128) as u64
     }
 
-    for day in 9..count {
-        res.insert(
-            day,
-            res.get(&(day - 7)).unwrap() + res.get(&(day - 9)).unwrap(),
-        );
+    fn mul(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 * *b as u128) % self.0 as u128) as u64
     }
+}
+
+/// How to get from one day's population to a later one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// One day at a time, which takes a single addition per day.
+    Step,
+    /// By raising the matrix of one day's changes to the number of days, in
+    /// O(log days) matrix products. Exact counts grow by a digit every 27
+    /// days or so, which makes the products slower than stepping for exact
+    /// counts, but modular counts stay small for any number of days.
+    Matrix,
+}
 
-    return res;
+type Matrix<T> = Vec<Vec<T>>;
+
+fn mat_mul<C: Counting>(
+    counting: &C,
+    a: &Matrix<C::Count>,
+    b: &Matrix<C::Count>,
+) -> Matrix<C::Count> {
+    let size = a.len();
+    (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| {
+                    (0..size).fold(counting.count(0), |acc, k| {
+                        counting.add(&acc, &counting.mul(&a[i][k], &b[k][j]))
+                    })
+                })
+                .collect()
+        })
+        .collect()
 }
 
-pub(crate) fn day6part1() -> usize {
-    let mut lines = read_lines("input6.t").unwrap();
-    let mut fish: Vec<_> = parse_numbers(&lines.next().unwrap().unwrap())
-        .iter()
-        .map(|&state| LanternFish { state })
+/// The matrix taking the fish counts by timer on one day to those `days`
+/// days later, for `timers` different timer values.
+fn transition<C: Counting>(
+    counting: &C,
+    lifecycle: &Lifecycle,
+    timers: usize,
+    mut days: u64,
+) -> Matrix<C::Count> {
+    let mut one_day: Matrix<C::Count> = vec![vec![counting.count(0); timers]; timers];
+    for timer in 1..timers {
+        one_day[timer - 1][timer] = counting.count(1);
+    }
+    for target in [lifecycle.reset, lifecycle.newborn] {
+        one_day[target][0] = counting.add(&one_day[target][0], &counting.count(1));
+    }
+
+    let mut result: Matrix<C::Count> = (0..timers)
+        .map(|i| {
+            (0..timers)
+                .map(|j| counting.count((i == j) as u64))
+                .collect()
+        })
         .collect();
-    for _ in 0..80 {
-        fish = next_day(fish);
+    while days > 0 {
+        if days & 1 == 1 {
+            result = mat_mul(counting, &result, &one_day);
+        }
+        one_day = mat_mul(counting, &one_day, &one_day);
+        days >>= 1;
+    }
+    return result;
+}
+
+/// How many fish there are with each timer value. Fish with the same timer
+/// all behave the same, so only their number matters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct School {
+    counts: Vec<u64>,
+}
+
+impl School {
+    pub fn from_timers(timers: &[usize]) -> Self {
+        let mut counts = vec![0; timers.iter().max().map_or(0, |&max| max + 1)];
+        for &timer in timers {
+            counts[timer] += 1;
+        }
+        return School { counts };
+    }
+
+    /// How many fish there are after `days` days, when they reproduce
+    /// following `lifecycle`.
+    pub fn population<C: Counting>(
+        &self,
+        lifecycle: &Lifecycle,
+        counting: &C,
+        days: u64,
+        method: Method,
+    ) -> C::Count {
+        let timers = lifecycle.timers().max(self.counts.len());
+        let mut counts: Vec<C::Count> = (0..timers)
+            .map(|timer| counting.count(*self.counts.get(timer).unwrap_or(&0)))
+            .collect();
+        match method {
+            Method::Step => {
+                for _ in 0..days {
+                    // the fish at 0 end up at the back, where nothing else
+                    // moves to, and from there go on to both of their timers
+                    counts.rotate_left(1);
+                    let due = std::mem::replace(&mut counts[timers - 1], counting.count(0));
+                    for target in [lifecycle.reset, lifecycle.newborn] {
+                        counts[target] = counting.add(&counts[target], &due);
+                    }
+                }
+            }
+            Method::Matrix => {
+                let matrix = transition(counting, lifecycle, timers, days);
+                counts = matrix
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .zip(&counts)
+                            .fold(counting.count(0), |acc, (m, c)| {
+                                counting.add(&acc, &counting.mul(m, c))
+                            })
+                    })
+                    .collect();
+            }
+        }
+        return counts
+            .iter()
+            .fold(counting.count(0), |acc, c| counting.add(&acc, c));
     }
-    return fish.len();
 }
 
-pub(crate) fn day6part2() -> usize {
-    let mut lines = read_lines("input6.t").unwrap();
-    let mut fish = parse_numbers(&lines.next().unwrap().unwrap());
-    let fish_by_day = by_day(256 + 7);
-    let total_count = fish
-        .iter()
-        .map(|state| fish_by_day.get(&(256 + (6 - state))).unwrap())
-        .sum();
-
-    println!("{:?}", total_count);
-    return total_count;
+/// Counts the lanternfish population after 80 and 256 days.
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Parsed = School;
+    type Part1 = BigUint;
+    type Part2 = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let line = match lines(input).next() {
+            Some(line) => line,
+            None => {
+                return Err(ParseError::new(
+                    1,
+                    "expected the fish timers, found nothing",
+                ))
+            }
+        };
+        let timers: Vec<usize> = line.parse(|text| separated(text, &[','], "a timer value"))?;
+        let max_timer = Lifecycle::LANTERNFISH.timers() - 1;
+        if let Some(idx) = timers.iter().position(|&t| t > max_timer) {
+            let reason = format!(
+                "fish timers run from 0 to {}, found {}",
+                max_timer, timers[idx]
+            );
+            let column = line
+                .text
+                .split(',')
+                .take(idx)
+                .map(|n| n.len() + 1)
+                .sum::<usize>()
+                + 1;
+            return Err(line.error(ParseError::new(column, reason)));
+        }
+        Ok(School::from_timers(&timers))
+    }
+
+    fn part1(school: &School) -> BigUint {
+        return school.population(&Lifecycle::LANTERNFISH, &Exact, 80, Method::Step);
+    }
+
+    fn part2(school: &School) -> BigUint {
+        let population = school.population(&Lifecycle::LANTERNFISH, &Exact, 256, Method::Step);
+        debug!("{} fish after 256 days", population);
+        return population;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "3,4,3,1,2";
+    const LANTERNFISH: Lifecycle = Lifecycle::LANTERNFISH;
+
+    #[test]
+    fn part1_example() {
+        let timers = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part1(&timers), BigUint::from(5934));
+    }
+
+    #[test]
+    fn part2_example() {
+        let timers = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part2(&timers), BigUint::from(26984457539));
+    }
+
+    #[test]
+    fn methods_agree() {
+        let school = Day6::parse(EXAMPLE).unwrap();
+        for days in [0, 1, 18, 256, 1000] {
+            assert_eq!(
+                school.population(&LANTERNFISH, &Exact, days, Method::Step),
+                school.population(&LANTERNFISH, &Exact, days, Method::Matrix),
+                "{} days",
+                days
+            );
+        }
+        let modulo = Modulo(1_000_000_007);
+        assert_eq!(
+            school.population(&LANTERNFISH, &modulo, 100_000, Method::Step),
+            school.population(&LANTERNFISH, &modulo, 100_000, Method::Matrix)
+        );
+    }
+
+    #[test]
+    fn other_lifecycles() {
+        let school = School::from_timers(&[0, 0, 0]);
+        // fish that give birth every day double every day
+        let daily = Lifecycle {
+            reset: 0,
+            newborn: 0,
+        };
+        assert_eq!(
+            school.population(&daily, &Exact, 10, Method::Step),
+            BigUint::from(3 << 10)
+        );
+        for lifecycle in [
+            daily,
+            Lifecycle {
+                reset: 2,
+                newborn: 2,
+            },
+            Lifecycle {
+                reset: 5,
+                newborn: 3,
+            },
+        ] {
+            assert_eq!(
+                school.population(&lifecycle, &Exact, 50, Method::Step),
+                school.population(&lifecycle, &Exact, 50, Method::Matrix),
+                "{:?}",
+                lifecycle
+            );
+        }
+        // fish whose timers start past the cycle only count down at first
+        let school = School::from_timers(&[8]);
+        let short = Lifecycle {
+            reset: 1,
+            newborn: 1,
+        };
+        assert_eq!(
+            school.population(&short, &Exact, 8, Method::Step),
+            BigUint::from(1)
+        );
+        assert_eq!(
+            school.population(&short, &Exact, 9, Method::Matrix),
+            BigUint::from(2)
+        );
+    }
+
+    #[test]
+    fn modular_counts_match_exact_ones() {
+        // far past where the population fits in a u64
+        let school = Day6::parse(EXAMPLE).unwrap();
+        let exact = school.population(&LANTERNFISH, &Exact, 1000, Method::Step);
+        assert_eq!(exact.to_u64(), None);
+        let modulo = Modulo(1_000_000_007);
+        let counted = school.population(&LANTERNFISH, &modulo, 1000, Method::Matrix);
+        assert_eq!(exact.rem(modulo.0), counted);
+    }
+
+    #[test]
+    fn counts_far_into_the_future() {
+        let school = Day6::parse(EXAMPLE).unwrap();
+        let modulo = Modulo(1_000_000_007);
+        assert!(school.population(&LANTERNFISH, &modulo, 10u64.pow(18), Method::Matrix) < modulo.0);
+    }
 }
@@ -1,55 +1,138 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader, Lines, Result},
-    path::Path,
-};
-
-fn parse_numbers(s: &str) -> Vec<isize> {
-    return s
-        .split(&[','][..])
-        .filter(|n| *n != "")
-        .map(|n| n.parse().unwrap())
-        .collect();
-}
-
-fn read_lines<P>(filename: P) -> Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
-}
-
-pub(crate) fn day7part1() -> isize {
-    let mut lines = read_lines("input7.t").unwrap();
-    let mut locations = parse_numbers(&lines.next().unwrap().unwrap());
-    println!("{}", locations.len());
-    locations.sort();
-    println!("{}", locations[500]);
-    let m1 = locations.iter().map(|&x| (x - locations[500]).abs()).sum();
-    let m2: isize = locations.iter().map(|&x| (x - locations[501]).abs()).sum();
-
-    println!("{} {}", m1, m2);
-
-    return m1;
-}
-
-fn cost_day_2(location: isize, dest: isize) -> isize {
-    let abs_dist = (location - dest).abs();
-    return ((abs_dist + 1) * abs_dist) / 2;
-}
-
-pub(crate) fn day7part2() -> isize {
-    let mut lines = read_lines("input7.t").unwrap();
-    let mut locations = parse_numbers(&lines.next().unwrap().unwrap());
-    let len = locations.len();
-    println!("{} {}", locations.iter().sum::<isize>(), len);
-    let avg1 = (locations.iter().sum::<isize>() as f32 / len as f32).floor() as isize;
-    let avg2 = (locations.iter().sum::<isize>() as f32 / len as f32).ceil() as isize;
-    println!("{} {}", avg1, avg2);
-    let m1: isize = locations.iter().map(|&x| cost_day_2(x, avg1)).sum();
-    let m2: isize = locations.iter().map(|&x| cost_day_2(x, avg2)).sum();
-    println!("{} {}", m1, m2);
-    return m1.min(m2);
+//! Day 7: The Treachery of Whales
+
+use crate::error::ParseError;
+use crate::info;
+use crate::input::{lines, separated};
+use crate::solution::Solution;
+
+/// Fuel for moving one step per unit of fuel, as in part 1.
+pub fn linear(distance: isize) -> isize {
+    return distance;
+}
+
+/// Fuel for moving when every step costs one more than the last, as in part 2.
+pub fn triangular(distance: isize) -> isize {
+    return (distance + 1) * distance / 2;
+}
+
+/// Fuel growing with the square of the distance, for crabs with other engines.
+pub fn quadratic(distance: isize) -> isize {
+    return distance * distance;
+}
+
+/// Where the crabs line up, and the fuel it takes them to get there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alignment {
+    pub position: isize,
+    pub fuel: isize,
+}
+
+fn total_fuel(positions: &[isize], position: isize, cost: &impl Fn(isize) -> isize) -> isize {
+    return positions.iter().map(|&x| cost((x - position).abs())).sum();
+}
+
+/// The cheapest position for every crab to move to, where moving a distance
+/// costs `cost(distance)` fuel, or `None` if there are no crabs.
+///
+/// `cost` has to be convex and never decrease, as the total fuel is then
+/// convex in the position too: it only goes down until the optimum and then
+/// only goes up. That lets a binary search find where it stops going down,
+/// between the outermost crabs, in O(n log(range)). Ties go to the lowest
+/// position.
+pub fn align(positions: &[isize], cost: impl Fn(isize) -> isize) -> Option<Alignment> {
+    let (mut low, mut high) = (*positions.iter().min()?, *positions.iter().max()?);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if total_fuel(positions, mid + 1, &cost) < total_fuel(positions, mid, &cost) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    return Some(Alignment {
+        position: low,
+        fuel: total_fuel(positions, low, &cost),
+    });
+}
+
+/// Aligns the crab submarines with the least fuel.
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Parsed = Vec<isize>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        match lines(input).next() {
+            Some(line) => line.parse(|text| {
+                let positions = separated(text, &[','], "a position")?;
+                if positions.is_empty() {
+                    return Err(ParseError::new(1, "expected at least one crab"));
+                }
+                Ok(positions)
+            }),
+            None => Err(ParseError::new(1, "expected crab positions, found nothing")),
+        }
+    }
+
+    fn part1(positions: &Vec<isize>) -> isize {
+        let alignment = align(positions, linear);
+        info!("aligning at {:?}", alignment);
+        return alignment.map_or(0, |a| a.fuel);
+    }
+
+    fn part2(positions: &Vec<isize>) -> isize {
+        let alignment = align(positions, triangular);
+        info!("aligning at {:?}", alignment);
+        return alignment.map_or(0, |a| a.fuel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn part1_example() {
+        let positions = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part1(&positions), 37);
+    }
+
+    #[test]
+    fn part2_example() {
+        let positions = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part2(&positions), 168);
+    }
+
+    #[test]
+    fn reports_position_and_fuel() {
+        let positions = Day7::parse(EXAMPLE).unwrap();
+        let expected = |position, fuel| Some(Alignment { position, fuel });
+        assert_eq!(align(&positions, linear), expected(2, 37));
+        assert_eq!(align(&positions, triangular), expected(5, 168));
+        assert_eq!(align(&[], linear), None);
+    }
+
+    #[test]
+    fn finds_the_optimum_for_any_convex_cost() {
+        let positions = [0, 1, 5, 9, 10, 10, 30, -4];
+        let costs: [fn(isize) -> isize; 4] = [linear, triangular, quadratic, |d| d * d * d + 3 * d];
+        for cost in costs {
+            let best = (-4..=30)
+                .map(|p| total_fuel(&positions, p, &cost))
+                .min()
+                .unwrap();
+            assert_eq!(align(&positions, cost).unwrap().fuel, best);
+        }
+    }
+
+    #[test]
+    fn ties_go_to_the_lowest_position() {
+        assert_eq!(align(&[2, 6], linear).unwrap().position, 2);
+    }
 }
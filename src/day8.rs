@@ -1,34 +1,50 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs::File,
-    io::{BufRead, BufReader, Lines, Result},
-    path::Path,
-};
-
-fn parse_digits(s: String) -> Vec<String> {
-    return s
-        .split(&['|', ' '][..])
-        .filter(|n| *n != "")
-        .map(|x| x.to_owned())
-        .collect();
-}
+//! Day 8: Seven Segment Search
+
+use std::collections::{HashMap, HashSet};
 
-fn read_lines<P>(filename: P) -> Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::Solution;
+use crate::{debug, trace};
+
+fn parse_digits(s: &str) -> Result<Vec<String>, ParseError> {
+    let (patterns, output) = s
+        .split_once('|')
+        .ok_or_else(|| ParseError::new(s.len() + 1, "expected '|' between patterns and output"))?;
+    let mut digits = Vec::new();
+    let mut column = 1;
+    for n in s.split(&['|', ' '][..]) {
+        if let Some(idx) = n.find(|c: char| !('a'..='g').contains(&c)) {
+            let c = n[idx..].chars().next().unwrap();
+            let reason = format!("expected a segment from a to g, found '{}'", c);
+            return Err(ParseError::new(column + idx, reason));
+        }
+        if !n.is_empty() {
+            digits.push(n.to_owned());
+        }
+        column += n.len() + 1;
+    }
+    let num_patterns = patterns.split_whitespace().count();
+    let num_output = output.split_whitespace().count();
+    if num_patterns != 10 || num_output != 4 {
+        let reason = format!(
+            "expected 10 patterns and 4 output digits, found {} and {}",
+            num_patterns, num_output
+        );
+        return Err(ParseError::new(1, reason));
+    }
+    return Ok(digits);
 }
 
-struct Entry {
+/// One display: its ten unique signal patterns and the four digit output.
+pub struct Entry {
     signal_patterns: Vec<String>,
     output_value: Vec<String>,
     map_num_to_pattern: HashMap<i32, String>,
     map_pattern_to_num: HashMap<String, i32>,
 }
 
-fn sorted_pattern(pattern: &String) -> String {
+fn sorted_pattern(pattern: &str) -> String {
     let mut pc = pattern.chars().collect::<Vec<_>>();
     pc.sort();
     pc.iter().collect()
@@ -40,7 +56,17 @@ impl Entry {
         self.map_pattern_to_num.insert(pattern.to_owned(), num);
     }
 
-    fn from_strings(line: Vec<String>) -> Self {
+    fn pattern_chars(&self, num: i32) -> Result<HashSet<char>, ParseError> {
+        match self.map_num_to_pattern.get(&num) {
+            Some(pattern) => Ok(pattern.chars().collect()),
+            None => Err(ParseError::new(
+                1,
+                format!("the signal patterns do not identify the digit {}", num),
+            )),
+        }
+    }
+
+    fn from_strings(line: Vec<String>) -> Result<Self, ParseError> {
         let mut res = Self {
             signal_patterns: line[0..10].iter().map(|f| sorted_pattern(f)).collect(),
             output_value: line[10..14].iter().map(|f| sorted_pattern(f)).collect(),
@@ -69,32 +95,30 @@ impl Entry {
                 _ => {}
             }
         }
-        let one_chars: HashSet<_> = res.map_num_to_pattern.get(&1).unwrap().chars().collect();
-        let four_chars: HashSet<_> = res.map_num_to_pattern.get(&4).unwrap().chars().collect();
+        let one_chars = res.pattern_chars(1)?;
+        let four_chars = res.pattern_chars(4)?;
 
         // we can now populate 2, 3, 6
         for p in &patterns {
             let c: HashSet<_> = p.chars().collect();
             match p.len() {
-                6 => {
-                    if !c.is_superset(&one_chars) {
-                        res.found_pattern(&p, 6);
-                    }
+                6 if !c.is_superset(&one_chars) => {
+                    res.found_pattern(p, 6);
                 }
                 5 => {
                     if c.union(&four_chars).collect::<HashSet<_>>().len() == 7 {
-                        res.found_pattern(&p, 2);
+                        res.found_pattern(p, 2);
                     } else if c.is_superset(&one_chars) {
-                        res.found_pattern(&p, 3);
+                        res.found_pattern(p, 3);
                     }
                 }
                 _ => {}
             }
         }
 
-        let three_chars: HashSet<_> = res.map_num_to_pattern.get(&3).unwrap().chars().collect();
+        let three_chars = res.pattern_chars(3)?;
         let four_and_three: HashSet<_> =
-            HashSet::from_iter(four_chars.union(&three_chars).map(|&x| x));
+            HashSet::from_iter(four_chars.union(&three_chars).copied());
 
         // we can now populate 0,5,9
         for p in &patterns {
@@ -108,9 +132,9 @@ impl Entry {
                     res.found_pattern(p, 5);
                 }
                 6 => {
-                    println!("{:?}, {:?}", c, four_and_three);
+                    trace!("{:?}, {:?}", c, four_and_three);
                     if c == four_and_three {
-                        println!("found 9");
+                        trace!("found 9");
                         res.found_pattern(p, 9);
                     } else {
                         res.found_pattern(p, 0);
@@ -120,10 +144,20 @@ impl Entry {
             }
         }
 
-        return res;
+        if let Some(unknown) = res
+            .output_value
+            .iter()
+            .find(|x| !res.map_pattern_to_num.contains_key(*x))
+        {
+            let reason = format!("output digit '{}' matches none of the patterns", unknown);
+            return Err(ParseError::new(1, reason));
+        }
+
+        return Ok(res);
     }
 
-    fn get_output_number(&self) -> i32 {
+    /// The four digit output value, decoded with the deduced wiring.
+    pub fn get_output_number(&self) -> i32 {
         let as_str: String = self.output_value.iter().fold("".to_owned(), |acc, x| {
             acc + &self.map_pattern_to_num.get(x).unwrap().to_string()
         });
@@ -131,7 +165,7 @@ impl Entry {
     }
 }
 
-fn match_pattern(pattern: &String) -> String {
+fn match_pattern(pattern: &str) -> String {
     let out = match pattern.len() {
         2 => "1",
         4 => "4",
@@ -142,7 +176,7 @@ fn match_pattern(pattern: &String) -> String {
 
     return out.to_owned();
 }
-/**
+/*
  *
   0:      1:      2:      3:      4:
  aaaa    ....    aaaa    aaaa    ....
@@ -173,22 +207,79 @@ has 6 segments and is not 6 or 9 => 0
 
  */
 
-pub(crate) fn day8part1() -> usize {
-    let mut lines = read_lines("input8.t").unwrap();
-    let input: Vec<_> = lines
-        .map(|line| Entry::from_strings(parse_digits(line.unwrap())).output_value)
-        .flat_map(|x| x)
-        .filter(|out_value| match_pattern(out_value) != "")
-        .collect();
-    println!("{:?}", input);
-    return input.len();
+/// Untangles the scrambled wires of the seven segment displays.
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Parsed = Vec<Entry>;
+    type Part1 = usize;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        lines(input)
+            .map(|line| line.parse(|text| parse_digits(text).and_then(Entry::from_strings)))
+            .collect()
+    }
+
+    fn part1(entries: &Vec<Entry>) -> usize {
+        let input: Vec<_> = entries
+            .iter()
+            .flat_map(|entry| entry.output_value.iter())
+            .filter(|out_value| !match_pattern(out_value).is_empty())
+            .collect();
+        debug!("{:?}", input);
+        return input.len();
+    }
+
+    fn part2(entries: &Vec<Entry>) -> i32 {
+        let input: Vec<_> = entries
+            .iter()
+            .map(|entry| entry.get_output_number())
+            .collect();
+        debug!("{:?}", input);
+        return input.iter().sum();
+    }
 }
 
-pub(crate) fn day8part2() -> i32 {
-    let mut lines = read_lines("input8.t").unwrap();
-    let mut input: Vec<_> = lines
-        .map(|line| Entry::from_strings(parse_digits(line.unwrap())).get_output_number())
-        .collect();
-    println!("{:?}", input);
-    return input.iter().sum();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+    // nine of the ten entries of the larger example
+    const LARGER_EXAMPLE: &str = "\
+be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    #[test]
+    fn decodes_example() {
+        let entries = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(entries[0].get_output_number(), 5353);
+    }
+
+    #[test]
+    fn part1_example() {
+        let entries = Day8::parse(LARGER_EXAMPLE).unwrap();
+        assert_eq!(Day8::part1(&entries), 22);
+    }
+
+    #[test]
+    fn part2_example() {
+        let entries = Day8::parse(LARGER_EXAMPLE).unwrap();
+        assert_eq!(
+            Day8::part2(&entries),
+            8394 + 9781 + 1197 + 9361 + 4873 + 4548 + 1625 + 8717 + 4315
+        );
+    }
 }
@@ -1,30 +1,21 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    fs::File,
-    io::{BufRead, BufReader, Lines, Result},
-    path::Path,
-};
-
-fn parse_numbers(s: &str) -> Vec<i32> {
-    return s.chars().map(|n| n.to_digit(10).unwrap() as i32).collect();
-}
+//! Day 9: Smoke Basin
 
-fn read_lines<P>(filename: P) -> Result<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
-}
+use std::collections::VecDeque;
+
+use crate::error::ParseError;
+use crate::grid::{Grid, Point};
+use crate::solution::{NoAnswer, Solution};
+use crate::{debug, info};
 
-const SIZE: usize = 100;
-struct HeightMap {
-    map: Vec<i32>,
+/// The heights of the cave floor.
+#[derive(Clone)]
+pub struct HeightMap {
+    map: Grid<i32>,
 }
 
 #[derive(Debug)]
 struct Location {
-    idx: usize,
+    point: Point,
     height: i32,
 }
 
@@ -35,53 +26,27 @@ impl Location {
 }
 
 impl HeightMap {
-    fn from_lines(lines: Lines<BufReader<File>>) -> Self {
-        let map: Vec<_> = lines
-            .map(|line| parse_numbers(&line.unwrap()))
-            .flat_map(|x| x)
-            .collect();
-        return Self { map };
+    fn from_lines(input: &str) -> Result<Self, ParseError> {
+        let map = Grid::parse_digits(input)?.map(|&height| height as i32);
+        return Ok(Self { map });
     }
 
-    fn get_neighbors(&self, idx: usize) -> Vec<Location> {
-        let mut res = Vec::new();
-        // not first row
-        if idx >= SIZE {
-            res.push(Location {
-                idx: idx - SIZE,
-                height: self.map[idx - SIZE],
-            })
-        }
-        // not first column
-        if idx % SIZE != 0 {
-            res.push(Location {
-                idx: idx - 1,
-                height: self.map[idx - 1],
-            })
-        }
-
-        // not last row
-        if idx < SIZE * (SIZE - 1) {
-            res.push(Location {
-                idx: idx + SIZE,
-                height: self.map[idx + SIZE],
+    fn get_neighbors(&self, point: Point) -> Vec<Location> {
+        return self
+            .map
+            .orthogonal_neighbors(point)
+            .map(|point| Location {
+                point,
+                height: self.map[point],
             })
-        }
-
-        // not last column
-        if idx % SIZE != SIZE - 1 {
-            res.push(Location {
-                idx: idx + 1,
-                height: self.map[idx + 1],
-            })
-        }
-
-        return res;
+            .collect();
     }
 
-    fn mark_basins(&mut self) -> i32 {
+    /// Replaces every height with the number of the basin it belongs to, and
+    /// every 9 with -1. Returns one past the highest basin number.
+    pub fn mark_basins(&mut self) -> i32 {
         // iterate through the heights - everything that isn't a nine becomes 0, nine becomes -1
-        for height in self.map.iter_mut() {
+        for height in self.map.values_mut() {
             if *height == 9 {
                 *height = -1;
             } else {
@@ -91,9 +56,10 @@ impl HeightMap {
 
         // now loop through all items that are 0s, and find the connected component
         let mut basin_num = 1;
-        for idx in 0..self.map.len() {
-            if self.map[idx] == 0 {
-                self.mark_neighbors_in_basin(idx, basin_num);
+        let points: Vec<_> = self.map.points().collect();
+        for point in points {
+            if self.map[point] == 0 {
+                self.mark_neighbors_in_basin(point, basin_num);
                 basin_num += 1;
             }
         }
@@ -101,54 +67,97 @@ impl HeightMap {
         return basin_num;
     }
 
-    fn mark_neighbors_in_basin(&mut self, idx: usize, basin_num: i32) {
+    fn mark_neighbors_in_basin(&mut self, point: Point, basin_num: i32) {
         let mut basin_neighbors = VecDeque::new();
-        basin_neighbors.push_back(idx);
-        while let Some(cur_idx) = basin_neighbors.pop_front() {
+        basin_neighbors.push_back(point);
+        while let Some(cur_point) = basin_neighbors.pop_front() {
             // find new neighbors that haven't been marked
-            for idx in self
-                .get_neighbors(cur_idx)
+            for point in self
+                .get_neighbors(cur_point)
                 .iter()
                 .filter(|&loc| loc.height == 0)
-                .map(|loc| loc.idx)
+                .map(|loc| loc.point)
             {
                 // mark the neighbors
-                basin_neighbors.push_back(idx);
-                self.map[idx] = basin_num;
+                basin_neighbors.push_back(point);
+                self.map[point] = basin_num;
             }
         }
     }
 }
 
-pub(crate) fn day9part1() -> usize {
-    let mut lines = read_lines("input9.t").unwrap();
-    let m = HeightMap::from_lines(lines);
-    let low_points: Vec<_> = m
-        .map
-        .iter()
-        .enumerate()
-        .map(|(idx, height)| Location {
-            idx,
-            height: *height,
-        })
-        .filter(|loc| loc.is_low_point(m.get_neighbors(loc.idx)))
-        .collect();
-    println!("{:?}", low_points);
-    return low_points.len() + low_points.iter().map(|x| x.height as usize).sum::<usize>();
+/// Finds the low points and basins of the cave floor.
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Parsed = HeightMap;
+    type Part1 = usize;
+    type Part2 = Result<usize, NoAnswer>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        HeightMap::from_lines(input)
+    }
+
+    fn part1(m: &HeightMap) -> usize {
+        let low_points: Vec<_> = m
+            .map
+            .iter()
+            .map(|(point, height)| Location {
+                point,
+                height: *height,
+            })
+            .filter(|loc| loc.is_low_point(m.get_neighbors(loc.point)))
+            .collect();
+        debug!("{:?}", low_points);
+        return low_points.len() + low_points.iter().map(|x| x.height as usize).sum::<usize>();
+    }
+
+    fn part2(m: &HeightMap) -> Result<usize, NoAnswer> {
+        let mut m = m.clone();
+        let num_basins = m.mark_basins();
+        info!("Found {} basins", num_basins);
+        let mut basin_sizes = Vec::<usize>::new();
+        for b in 1..num_basins {
+            basin_sizes.push(m.map.values().filter(|&x| *x == b).count());
+        }
+
+        if basin_sizes.len() < 3 {
+            return Err(NoAnswer("there are fewer than three basins"));
+        }
+        basin_sizes.sort();
+        basin_sizes.reverse();
+
+        return Ok(basin_sizes[0] * basin_sizes[1] * basin_sizes[2]);
+    }
 }
 
-pub(crate) fn day9part2() -> usize {
-    let lines = read_lines("input9.t").unwrap();
-    let mut m = HeightMap::from_lines(lines);
-    let num_basins = m.mark_basins();
-    println!("Found {} basins", num_basins);
-    let mut basin_sizes = Vec::<usize>::new();
-    for b in 1..num_basins {
-        basin_sizes.push(m.map.iter().filter(|&x| *x == b).count());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+    #[test]
+    fn part1_example() {
+        let map = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part1(&map), 15);
     }
 
-    basin_sizes.sort();
-    basin_sizes.reverse();
+    #[test]
+    fn part2_example() {
+        let map = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part2(&map), Ok(1134));
+    }
 
-    return basin_sizes[0] * basin_sizes[1] * basin_sizes[2];
+    #[test]
+    fn no_answer_with_fewer_than_three_basins() {
+        let map = Day9::parse("99\n19").unwrap();
+        assert!(Day9::part2(&map).is_err());
+    }
 }
@@ -0,0 +1,142 @@
+//! Errors raised while reading and parsing puzzle input.
+
+use std::{fmt::Display, io, str::FromStr};
+
+/// Malformed puzzle input. Line parsers create it with the column and reason,
+/// the loop reading the lines adds the line, and the runner adds the day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    day: Option<u8>,
+    line: Option<usize>,
+    column: usize,
+    text: String,
+    reason: String,
+}
+
+impl ParseError {
+    /// An error at `column` (counting from 1) of the line being parsed.
+    pub fn new(column: usize, reason: impl Into<String>) -> Self {
+        ParseError {
+            day: None,
+            line: None,
+            column,
+            text: String::new(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Records which line (counting from 1) the error is on, and its contents.
+    pub fn at_line(mut self, line: usize, text: &str) -> Self {
+        self.line = Some(line);
+        self.text = text.to_owned();
+        self
+    }
+
+    pub fn for_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "line {}, ", line)?;
+        }
+        write!(f, "column {}: {}", self.column, self.reason)?;
+        if self.line.is_some() {
+            let indent = " ".repeat(self.column.saturating_sub(1));
+            write!(f, "\n    {}\n    {}^", self.text, indent)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a single field of a line, where `column` is where the field starts.
+pub fn parse_field<T: FromStr>(
+    field: &str,
+    column: usize,
+    expected: &str,
+) -> Result<T, ParseError> {
+    field.parse().map_err(|_| {
+        if field.is_empty() {
+            ParseError::new(column, format!("expected {}, found nothing", expected))
+        } else {
+            ParseError::new(column, format!("expected {}, found '{}'", expected, field))
+        }
+    })
+}
+
+/// Everything that can go wrong while running a solver.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Io(io::Error),
+    /// The input was read, but is not valid for the day.
+    Parse(ParseError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_column_and_reason() {
+        let err = ParseError::new(3, "expected a digit");
+        assert_eq!(err.to_string(), "column 3: expected a digit");
+    }
+
+    #[test]
+    fn points_at_the_column() {
+        let err = ParseError::new(3, "expected a digit")
+            .at_line(2, "12x4")
+            .for_day(9);
+        assert_eq!(
+            err.to_string(),
+            "day 9, line 2, column 3: expected a digit\n    12x4\n      ^"
+        );
+        let err = ParseError::new(1, "expected a digit").at_line(1, "x");
+        assert!(err.to_string().ends_with("\n    x\n    ^"));
+    }
+
+    #[test]
+    fn parses_fields() {
+        assert_eq!(parse_field::<u32>("42", 1, "a number"), Ok(42));
+        assert_eq!(
+            parse_field::<u32>("4x", 5, "a number"),
+            Err(ParseError::new(5, "expected a number, found '4x'"))
+        );
+        assert_eq!(
+            parse_field::<u32>("", 5, "a number"),
+            Err(ParseError::new(5, "expected a number, found nothing"))
+        );
+    }
+}
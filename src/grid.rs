@@ -0,0 +1,278 @@
+//! A rectangular grid of cells, such as the height map of day 9 or the
+//! octopus garden of day 11, indexed by `(x, y)` from the top left corner.
+
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+use crate::input::{lines, Line};
+
+/// A position in a grid: column `x`, then row `y`.
+pub type Point = (usize, usize);
+
+/// Up, left, right and down.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The orthogonal directions and the four diagonals, clockwise from the top left.
+const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// Cells laid out in rows of equal width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid from its cells, row by row. Panics if there are not exactly
+    /// `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "grid cells do not fill the grid"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one row of the grid from each line. Every row must be as wide
+    /// as the first one, and there must be at least one row.
+    pub fn from_lines<'a>(
+        lines: impl IntoIterator<Item = Line<'a>>,
+        parse_row: impl Fn(&'a str) -> Result<Vec<T>, ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for line in lines {
+            let row = line.parse(&parse_row)?;
+            if height == 0 {
+                width = row.len();
+            } else if row.len() != width {
+                let reason = format!("expected {} cells in a row, found {}", width, row.len());
+                return Err(line.error(ParseError::new(1, reason)));
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        if width == 0 {
+            return Err(ParseError::new(1, "expected a grid, found nothing"));
+        }
+        return Ok(Grid::new(width, height, cells));
+    }
+
+    /// Parses a map with one character per cell, where `cell` turns a
+    /// character into a cell and `expected` describes a valid character.
+    pub fn parse_chars(
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Grid::from_lines(lines(input), |text| {
+            text.chars()
+                .enumerate()
+                .map(|(idx, c)| {
+                    cell(c).ok_or_else(|| {
+                        ParseError::new(idx + 1, format!("expected {}, found '{}'", expected, c))
+                    })
+                })
+                .collect()
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Point) -> Option<&T> {
+        if x < self.width && y < self.height {
+            return Some(&self.cells[y * self.width + x]);
+        }
+        return None;
+    }
+
+    pub fn get_mut(&mut self, (x, y): Point) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            return Some(&mut self.cells[y * self.width + x]);
+        }
+        return None;
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |idx| (idx % width, idx / width))
+    }
+
+    /// Every cell along with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Every cell, row by row.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x`, top to bottom. Panics if `x` is outside of
+    /// the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} outside of the grid", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// The up to four points above, below and to either side of `point`.
+    pub fn orthogonal_neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |&direction| self.step(point, direction))
+    }
+
+    /// The up to eight points around `point`, diagonals included.
+    pub fn all_neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .filter_map(move |&direction| self.step(point, direction))
+    }
+
+    /// A grid of the same shape, with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    fn step(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Option<Point> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if x < self.width && y < self.height {
+            return Some((x, y));
+        }
+        return None;
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a map with a single digit per cell, such as `2199943210`.
+    pub fn parse_digits(input: &str) -> Result<Self, ParseError> {
+        Grid::parse_chars(input, "a digit", |c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).expect("point outside of the grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point).expect("point outside of the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // three wide and two high
+    const DIGITS: &str = "123\n456";
+
+    #[test]
+    fn parses_rows_and_columns() {
+        let grid = Grid::parse_digits(DIGITS).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], 3);
+        assert_eq!(grid[(0, 1)], 4);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        let columns: Vec<Vec<u8>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(grid.points().last(), Some((2, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 outside of the grid")]
+    fn column_outside_of_the_grid() {
+        let grid = Grid::parse_digits(DIGITS).unwrap();
+        let _ = grid.column(3);
+    }
+
+    #[test]
+    fn counts_neighbors_at_corners_and_edges() {
+        let grid = Grid::parse_digits(DIGITS).unwrap();
+        let counts = |point| {
+            (
+                grid.orthogonal_neighbors(point).count(),
+                grid.all_neighbors(point).count(),
+            )
+        };
+        assert_eq!(counts((0, 0)), (2, 3));
+        assert_eq!(counts((2, 1)), (2, 3));
+        assert_eq!(counts((1, 0)), (3, 5));
+        let mut neighbors: Vec<Point> = grid.orthogonal_neighbors((1, 1)).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![(0, 1), (1, 0), (2, 1)]);
+
+        let tall = Grid::parse_digits("1\n2\n3").unwrap();
+        assert_eq!(tall.all_neighbors((0, 1)).count(), 2);
+    }
+
+    #[test]
+    fn rejects_ragged_and_empty_input() {
+        let err = Grid::parse_digits("123\n45").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(1, "expected 3 cells in a row, found 2").at_line(2, "45")
+        );
+        let err = Grid::parse_digits("12\n3x").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, "expected a digit, found 'x'").at_line(2, "3x")
+        );
+        assert!(Grid::parse_digits("").is_err());
+    }
+
+    #[test]
+    fn maps_cells() {
+        let grid = Grid::parse_digits(DIGITS).unwrap().map(|&d| d % 2 == 0);
+        assert_eq!(grid.values().filter(|&&even| even).count(), 3);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+    }
+}
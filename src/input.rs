@@ -0,0 +1,199 @@
+//! Reading puzzle input, and splitting it into the pieces the days parse:
+//! numbered lines, blank line separated sections and separated value lists.
+
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use crate::error::{parse_field, ParseError};
+
+/// Where a solver reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Path(PathBuf),
+    Stdin,
+}
+
+/// stdin can only be consumed once, so it is read in full on first use and
+/// shared by every solver that runs afterwards (e.g. both parts of a day).
+static STDIN: OnceLock<String> = OnceLock::new();
+
+impl Input {
+    /// Interprets a command line argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Input::Stdin,
+            path => Input::Path(PathBuf::from(path)),
+        }
+    }
+
+    /// The checked in `inputN.t` for a day.
+    pub fn default_for(day: u8) -> Self {
+        Input::in_crate(&default_name(day))
+    }
+
+    /// A file checked in next to the puzzle inputs. It is resolved against the
+    /// crate root rather than the working directory, so the runner works from anywhere.
+    pub fn in_crate(name: &str) -> Self {
+        Input::Path(Path::new(env!("CARGO_MANIFEST_DIR")).join(name))
+    }
+
+    /// Whether there is anything to read. Days whose puzzle input has not been
+    /// checked in yet only have their examples.
+    pub fn exists(&self) -> bool {
+        match self {
+            Input::Path(path) => path.exists(),
+            Input::Stdin => true,
+        }
+    }
+
+    /// Reads the whole input.
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Input::Path(path) => fs::read_to_string(path),
+            Input::Stdin => {
+                if STDIN.get().is_none() {
+                    let mut buf = String::new();
+                    io::stdin().read_to_string(&mut buf)?;
+                    let _ = STDIN.set(buf);
+                }
+                Ok(STDIN.get().unwrap().clone())
+            }
+        }
+    }
+}
+
+/// The file name of a day's checked in input, such as `input9.t`.
+pub fn default_name(day: u8) -> String {
+    format!("input{}.t", day)
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::Path(path) => write!(f, "{}", path.display()),
+            Input::Stdin => f.write_str("<stdin>"),
+        }
+    }
+}
+
+/// A line of input, along with its line number counting from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Parses the text of the line, placing any error on this line.
+    pub fn parse<T>(
+        &self,
+        parse: impl FnOnce(&'a str) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        parse(self.text).map_err(|err| self.error(err))
+    }
+
+    /// Places an error found in the text of the line on this line.
+    pub fn error(&self, err: ParseError) -> ParseError {
+        err.at_line(self.number, self.text)
+    }
+}
+
+/// Every line of `text`, numbered.
+pub fn lines(text: &str) -> impl Iterator<Item = Line<'_>> {
+    text.lines().enumerate().map(|(idx, text)| Line {
+        number: idx + 1,
+        text,
+    })
+}
+
+/// Groups of consecutive lines, split wherever there are blank lines.
+pub fn sections(text: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = Vec::new();
+    let mut current = Vec::new();
+    for line in lines(text) {
+        if line.text.trim().is_empty() {
+            if !current.is_empty() {
+                sections.push(current);
+                current = Vec::new();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        sections.push(current);
+    }
+    return sections;
+}
+
+/// Values separated by any of `separators`, where repeated separators (such as
+/// the spaces aligning a bingo card) count as one. `expected` describes a
+/// value for error messages.
+pub fn separated<T: FromStr>(
+    text: &str,
+    separators: &[char],
+    expected: &str,
+) -> Result<Vec<T>, ParseError> {
+    let mut values = Vec::new();
+    let mut column = 1;
+    for field in text.split(separators) {
+        if !field.is_empty() {
+            values.push(parse_field(field, column, expected)?);
+        }
+        column += field.len() + 1;
+    }
+    return Ok(values);
+}
+
+/// A comma separated list of integers, such as `3,4,3,1,2`.
+pub fn comma_separated<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    separated(text, &[','], "an integer")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_sections_keeping_line_numbers() {
+        let sections = sections("a\nb\n\n\nc\n   \nd\n");
+        let numbers: Vec<Vec<usize>> = sections
+            .iter()
+            .map(|section| section.iter().map(|line| line.number).collect())
+            .collect();
+        assert_eq!(numbers, vec![vec![1, 2], vec![5], vec![7]]);
+        assert!(super::sections("\n\n").is_empty());
+    }
+
+    #[test]
+    fn separated_skips_repeated_separators() {
+        let values: Vec<u32> = separated(" 1  22 3", &[' '], "a number").unwrap();
+        assert_eq!(values, vec![1, 22, 3]);
+        let values: Vec<u32> = separated("1 -> 2,3", &[' ', '-', '>', ','], "a number").unwrap();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn separated_reports_the_field_column() {
+        let err = separated::<u32>(" 1  x", &[' '], "a number").unwrap_err();
+        assert_eq!(err, ParseError::new(5, "expected a number, found 'x'"));
+        let err = comma_separated::<u32>("3,4,,x").unwrap_err();
+        assert_eq!(err, ParseError::new(6, "expected an integer, found 'x'"));
+    }
+
+    #[test]
+    fn line_errors_carry_the_line() {
+        let line = lines("1\n2x").nth(1).unwrap();
+        let err = line.parse(comma_separated::<u32>).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(1, "expected an integer, found '2x'").at_line(2, "2x")
+        );
+    }
+}
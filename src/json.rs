@@ -0,0 +1,80 @@
+//! Just enough JSON to write flat records, such as the runner's `--format json`.
+
+use std::fmt::{Display, Write};
+
+/// A JSON object whose fields are written in the order they were added.
+#[derive(Debug, Default, Clone)]
+pub struct Object {
+    fields: Vec<(String, String)>,
+}
+
+impl Object {
+    pub fn new() -> Self {
+        Object::default()
+    }
+
+    pub fn string(mut self, key: &str, value: &str) -> Self {
+        self.fields.push((key.to_owned(), quote(value)));
+        self
+    }
+
+    pub fn number(mut self, key: &str, value: impl Into<u128>) -> Self {
+        self.fields.push((key.to_owned(), value.into().to_string()));
+        self
+    }
+}
+
+impl Display for Object {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char('{')?;
+        for (idx, (key, value)) in self.fields.iter().enumerate() {
+            if idx > 0 {
+                f.write_char(',')?;
+            }
+            write!(f, "{}:{}", quote(key), value)?;
+        }
+        f.write_char('}')
+    }
+}
+
+/// `s` as a JSON string literal, quotes included.
+pub fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    return quoted;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_special_characters() {
+        assert_eq!(quote("plain"), "\"plain\"");
+        assert_eq!(quote("a \"b\"\\c"), "\"a \\\"b\\\"\\\\c\"");
+        assert_eq!(quote("#..#\n.##.\u{1}"), "\"#..#\\n.##.\\u0001\"");
+    }
+
+    #[test]
+    fn writes_fields_in_order() {
+        let object = Object::new()
+            .number("day", 2u8)
+            .string("answer", "Location { horizontal: 15 }");
+        assert_eq!(
+            object.to_string(),
+            "{\"day\":2,\"answer\":\"Location { horizontal: 15 }\"}"
+        );
+    }
+}
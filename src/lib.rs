@@ -0,0 +1,56 @@
+//! Solutions to Advent of Code 2021.
+//!
+//! Every day lives in its own module and implements [`solution::Solution`]:
+//! its input is parsed once into a domain type (a [`day9::HeightMap`], a
+//! [`day12::Graph`], ...) and both parts are solved from that value. The
+//! [`registry`] lists every solved day for runners such as the `aoc2021` binary.
+//!
+//! ```no_run
+//! use aoc2021::day9::Day9;
+//! use aoc2021::input::Input;
+//! use aoc2021::solution::Solution;
+//!
+//! let text = Input::default_for(9).read().unwrap();
+//! let map = Day9::parse(&text).unwrap();
+//! println!("risk level: {}", Day9::part1(&map));
+//! ```
+
+#![allow(clippy::needless_return)]
+
+pub mod answers;
+pub mod bench;
+pub mod bigint;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod json;
+pub mod log;
+pub mod ocr;
+pub mod registry;
+pub mod scaffold;
+pub mod solution;
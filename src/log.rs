@@ -0,0 +1,147 @@
+//! Diagnostic output from the solvers, filtered by a global verbosity level.
+//!
+//! Messages go to stderr so that stdout only ever holds answers. Log with the
+//! [`info!`](crate::info), [`debug!`](crate::debug) and [`trace!`](crate::trace)
+//! macros, which take `format!` style arguments.
+
+use std::{
+    env,
+    fmt::{self, Display},
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// The environment variable read by [`init_from_env`].
+pub const ENV_VAR: &str = "AOC2021_LOG";
+
+/// How much to log, where each level includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Nothing but the answers.
+    Quiet,
+    /// A summary line or two per part, such as how many basins were found.
+    Info,
+    /// Intermediate results, such as every path through the caves.
+    Debug,
+    /// Every step, such as the octopus garden after each step.
+    Trace,
+}
+
+const LEVELS: [Level; 4] = [Level::Quiet, Level::Info, Level::Debug, Level::Trace];
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Quiet as u8);
+
+impl Level {
+    /// The next more verbose level, if there is one.
+    pub fn more_verbose(self) -> Level {
+        LEVELS[(self as usize + 1).min(LEVELS.len() - 1)]
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Quiet => "quiet",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        })
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LEVELS
+            .iter()
+            .copied()
+            .find(|level| level.to_string() == s.to_lowercase())
+            .ok_or_else(|| {
+                format!(
+                    "unknown log level '{}', expected quiet, info, debug or trace",
+                    s
+                )
+            })
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    LEVELS[LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Quiet && level <= self::level()
+}
+
+/// Sets the level from the environment variable, if it is set.
+pub fn init_from_env() -> Result<(), String> {
+    if let Ok(value) = env::var(ENV_VAR) {
+        set_level(
+            value
+                .parse()
+                .map_err(|err| format!("{}: {}", ENV_VAR, err))?,
+        );
+    }
+    Ok(())
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, args: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("[{}] {}", level, args);
+    }
+}
+
+/// Logs a summary message.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::log::write($crate::log::Level::Info, format_args!($($arg)*))
+    };
+}
+
+/// Logs an intermediate result.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::log::write($crate::log::Level::Debug, format_args!($($arg)*))
+    };
+}
+
+/// Logs a single step.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::log::write($crate::log::Level::Trace, format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_levels_in_any_case() {
+        assert_eq!("quiet".parse(), Ok(Level::Quiet));
+        assert_eq!("Info".parse(), Ok(Level::Info));
+        assert_eq!("DEBUG".parse(), Ok(Level::Debug));
+        assert_eq!("trace".parse(), Ok(Level::Trace));
+        assert_eq!(
+            "loud".parse::<Level>(),
+            Err("unknown log level 'loud', expected quiet, info, debug or trace".to_string())
+        );
+    }
+
+    #[test]
+    fn more_verbose_stops_at_trace() {
+        assert_eq!(Level::Quiet.more_verbose(), Level::Info);
+        assert_eq!(Level::Info.more_verbose(), Level::Debug);
+        assert_eq!(Level::Debug.more_verbose(), Level::Trace);
+        assert_eq!(Level::Trace.more_verbose(), Level::Trace);
+    }
+}
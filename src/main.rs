@@ -1,161 +1,613 @@
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-use day3::day3part1;
-use std::fmt::Debug;
-use std::io::BufRead;
-use std::{fs::File, io, path::Path};
-
-use crate::day10::{day10part1, day10part2};
-use crate::day11::{day11part1, day11part2};
-use crate::day12::{day12part1, day12part2};
-use crate::day13::day13part1;
-use crate::day3::day3part2;
-use crate::day4::{day4part1, day4part2};
-use crate::day5::day5part1;
-use crate::day6::{day6part1, day6part2};
-use crate::day7::{day7part1, day7part2};
-use crate::day8::{day8part1, day8part2};
-use crate::day9::{day9part1, day9part2};
+#![allow(clippy::needless_return)]
+
+use std::{env, error::Error, path::PathBuf, process, str::FromStr};
+
+use aoc2021::answers::{self, Answers, Outcome};
+use aoc2021::bench::{self, Baseline};
+use aoc2021::input::Input;
+use aoc2021::log::{self, Level};
+use aoc2021::registry::{self, Job};
+use aoc2021::scaffold;
+
+const USAGE: &str = "usage:
+    aoc2021 run --day <N> [--part <M>] [--input <FILE>]   run one day, or a single part of it
+    aoc2021 run --all                                     run every solved day and part
+    aoc2021 verify [--day <N>] [--answers <FILE>]         check answers against the recorded ones
+    aoc2021 bench [--day <N> [--part <M>]] [--runs <N>] [--save] [--compare]
+                                                          time parsing and each part over several runs
+    aoc2021 new --day <N>                                 create the module, registry entry and input file
+                                                          for a new day
+
+options:
+    -i, --input <FILE>   read the puzzle input from FILE, or from stdin if FILE is '-'
+                         (defaults to the day's inputN.t in the crate root)
+    --format <FORMAT>    print answers from run as text (the default) or json, one object per
+                         line with the fields day, part, answer, type, input, parse_ns and solve_ns,
+                         where type is integer, text, tuple or none
+    --answers <FILE>     the recorded answers (defaults to answers.txt in the crate root)
+    --runs <N>           how many times to parse and solve each day (defaults to 10)
+    --save               save the median timings as the baseline
+    --compare            compare the median timings with the baseline, failing if any stage
+                         is more than 10% slower
+    --baseline <FILE>    where the baseline is kept (defaults to target/bench-baseline.txt)
+
+logging, accepted by every command:
+    -v, --verbose        log more to stderr, repeat for more detail (-v info, -vv debug, -vvv trace)
+    -q, --quiet          log nothing, only print the answers (the default)
+    --log <LEVEL>        log at quiet, info, debug or trace
+                         (defaults to the AOC2021_LOG environment variable)";
+
+const DEFAULT_RUNS: usize = 10;
+
+/// How much slower than the baseline a stage may get before `bench --compare` fails.
+const REGRESSION_PERCENT: f64 = 10.0;
+
+/// How `run` prints the answers.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Which solvers the user asked to run.
+#[derive(Debug, PartialEq)]
+enum Selection {
+    All,
+    Day(u8),
+    Part(u8, u8),
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run {
+        selection: Selection,
+        input: Option<Input>,
+        format: Format,
+    },
+    Verify {
+        day: Option<u8>,
+        answers: Option<PathBuf>,
+    },
+    Bench {
+        selection: Selection,
+        runs: usize,
+        save: bool,
+        compare: bool,
+        baseline: Option<PathBuf>,
+    },
+    New {
+        day: u8,
+    },
+    Help,
+}
 
 fn main() {
-    // println!("day1part1 {}", day1part1());
-    // println!("day1part2 {}", day1part2());
-
-    // println!("day2part2 {:?}", day2part2());
-    // println!("day3part1 {:?}", day3part1());
-    // println!("day3part2 {:?}", day3part2());
-    // println!("day4part1 {:?}", day4part1());
-    // println!("day4part2 {:?}", day4part2());
-    // println!("day5part1 {:?}", day5part1());
-    // println!("day6part1 {:?}", day6part1());
-    // println!("day6part2 {:?}", day6part2());
-    // println!("day7part1 {:?}", day7part1());
-    // println!("day7part2 {:?}", day7part2());
-    // println!("day8part1 {:?}", day8part1());
-    // println!("day8part2 {:?}", day8part2());
-    // println!("day9part1 {:?}", day9part1());
-    // println!("day9part2 {:?}", day9part2());
-    // println!("day10part1 {:?}", day10part1());
-    // println!("day10part2 {:?}", day10part2());
-    // println!("day11part1 {:?}", day11part1());
-    // println!("day11part2 {:?}", day11part2());
-    // println!("day12part1 {:?}", day12part1());
-    // println!("day12part2 {:?}", day12part2());
-    println!("day13part1 {:?}", day13part1());
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn day1part1() -> i32 {
-    let x: Vec<i32> = read_lines("input1.t")
-        .unwrap()
-        .map(|l| l.unwrap().parse().unwrap())
-        .collect();
-    let mut i1 = x.iter();
-    i1.next();
-    return i1.zip(x.iter()).map(|m| (m.0 > m.1) as i32).sum();
-}
-
-fn day1part2() -> i32 {
-    let x: Vec<i32> = read_lines("input1.t")
-        .unwrap()
-        .map(|l| l.unwrap().parse().unwrap())
-        .collect();
-    let window_size = 3;
-    let num_extra = window_size - 1;
-
-    // create a vector of slices, each the size of the desired window
-    let windows = (0..x.len() - num_extra)
-        .map(|idx| &x[idx..(idx + window_size)])
-        .collect::<Vec<&[i32]>>();
-
-    // sum each window
-    let sums: Vec<i32> = windows.iter().map(|w| w.iter().sum()).collect();
-    // println!(
-    //     "number of measurements {} number of sums {}",
-    //     x.len(),
-    //     sums.len()
-    // );
-
-    // println!("{:?}", &x[0..12]);
-    // println!("{:?}", &sums[0..10]);
-
-    let mut i1 = sums.iter();
-    i1.next();
-    return i1.zip(sums.iter()).map(|m| (m.0 > m.1) as i32).sum();
-}
-
-fn day2part2() -> Location {
-    let lines = read_lines("input2.t").unwrap();
-    let mut location = Location {
-        horizontal: 0,
-        depth: 0,
-        aim: 0,
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = match set_log_level(args) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Help => println!("{}", USAGE),
+        Command::Run {
+            selection,
+            input,
+            format,
+        } => {
+            if let Err(err) = run(selection, input, format) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        Command::Verify { day, answers } => match verify(day, answers) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        },
+        Command::Bench {
+            selection,
+            runs,
+            save,
+            compare,
+            baseline,
+        } => match run_bench(selection, runs, save, compare, baseline) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        },
+        Command::New { day } => {
+            if let Err(err) = new_day(day) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn jobs(selection: Selection) -> Result<Vec<Job>, Box<dyn Error>> {
+    let jobs = match selection {
+        Selection::All => registry::all(),
+        Selection::Day(day) => vec![registry::for_day(day)?],
+        Selection::Part(day, part) => vec![registry::find(day, part)?],
+    };
+    return Ok(jobs);
+}
+
+fn run(selection: Selection, input: Option<Input>, format: Format) -> Result<(), Box<dyn Error>> {
+    let every_day = selection == Selection::All;
+    for job in jobs(selection)? {
+        let input = match &input {
+            Some(input) => input.clone(),
+            None => Input::default_for(job.day()),
+        };
+        if skip_missing(every_day, &input) {
+            continue;
+        }
+        let text = input.read().map_err(|err| format!("{}: {}", input, err))?;
+        let (parse_time, answers) = job
+            .solver
+            .solve(&text, &job.parts)
+            .map_err(|err| format!("{}: {}", input, err))?;
+        for answer in answers {
+            match format {
+                Format::Text => println!("day{}part{} {}", job.day(), answer.part, answer.value),
+                Format::Json => {
+                    let record = answer.record(job.day(), &input.to_string(), parse_time);
+                    println!("{}", record);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// When running every day, days whose input is not checked in are skipped
+/// with a note rather than stopping the run.
+fn skip_missing(every_day: bool, input: &Input) -> bool {
+    if !every_day || input.exists() {
+        return false;
+    }
+    eprintln!("skipping {}: not found", input);
+    return true;
+}
+
+/// Checks every answer against the recorded ones, returning whether they all
+/// passed. Missing answers, and days without an input, are reported but do
+/// not count as failures.
+fn verify(day: Option<u8>, answers: Option<PathBuf>) -> Result<bool, Box<dyn Error>> {
+    let jobs = match day {
+        Some(day) => vec![registry::for_day(day)?],
+        None => registry::all(),
+    };
+    let path = answers.unwrap_or_else(answers::default_path);
+    let answers = Answers::load(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+    let (mut passed, mut failed, mut missing, mut no_input) = (0, 0, 0, 0);
+    for check in answers::verify(&answers, &jobs) {
+        let name = format!("day{}part{} {}", check.day, check.part, check.input);
+        match check.outcome {
+            Outcome::Pass => {
+                passed += 1;
+                println!("pass     {} ({:.2?})", name, check.elapsed);
+            }
+            Outcome::Fail { expected, actual } => {
+                failed += 1;
+                println!("FAIL     {} ({:.2?})", name, check.elapsed);
+                println!("         expected {}", expected);
+                println!("         got      {}", actual);
+            }
+            Outcome::Missing { actual } => {
+                missing += 1;
+                println!("missing  {} ({:.2?}): got {}", name, check.elapsed, actual);
+            }
+            Outcome::Error(err) => {
+                failed += 1;
+                println!("ERROR    {}: {}", name, err);
+            }
+            Outcome::NoInput => {
+                no_input += 1;
+                println!("no input {}", name);
+            }
+        }
+    }
+    println!(
+        "{} passed, {} failed, {} missing, {} without input",
+        passed, failed, missing, no_input
+    );
+    return Ok(failed == 0);
+}
+
+/// Times the selected days, returning false if comparing against the baseline
+/// found a regression.
+fn run_bench(
+    selection: Selection,
+    runs: usize,
+    save: bool,
+    compare: bool,
+    baseline_path: Option<PathBuf>,
+) -> Result<bool, Box<dyn Error>> {
+    let path = baseline_path.unwrap_or_else(bench::default_baseline_path);
+    let mut baseline = if compare || (save && path.exists()) {
+        Baseline::load(&path).map_err(|err| format!("{}: {}", path.display(), err))?
+    } else {
+        Baseline::default()
     };
-    let commands: Vec<_> = lines.map(|l| parse_command(l.unwrap()).unwrap()).collect();
-    for command in commands {
-        location = location.process_command(&command);
+
+    let mut regressions = 0;
+    let mut all_timings = Vec::new();
+    let every_day = selection == Selection::All;
+    for job in jobs(selection)? {
+        let input = Input::default_for(job.day());
+        if skip_missing(every_day, &input) {
+            continue;
+        }
+        let timings =
+            bench::bench(&job, &input, runs).map_err(|err| format!("{}: {}", input, err))?;
+        for timing in &timings {
+            let mut line = format!(
+                "day{:<3} {:<6} min {:>10.2?}  median {:>10.2?}",
+                timing.day,
+                timing.stage.to_string(),
+                timing.min,
+                timing.median
+            );
+            if compare {
+                match baseline.median(timing.day, timing.stage) {
+                    Some(saved) => {
+                        let change = bench::change_percent(saved, timing.median);
+                        line += &format!("  {:+.1}% vs {:.2?}", change, saved);
+                        if change > REGRESSION_PERCENT {
+                            regressions += 1;
+                            line += "  REGRESSED";
+                        }
+                    }
+                    None => line += "  (no baseline)",
+                }
+            }
+            println!("{}", line);
+        }
+        all_timings.extend(timings);
+    }
+
+    if save {
+        baseline.record(&all_timings);
+        baseline
+            .save(&path)
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        println!("saved baseline to {}", path.display());
     }
-    return location;
+    if compare {
+        println!(
+            "{} stages regressed by more than {}%",
+            regressions, REGRESSION_PERCENT
+        );
+    }
+    return Ok(regressions == 0);
 }
 
-enum Direction {
-    Forward,
-    Down,
-    Up,
+/// Generates a new day in this crate and lists the files it touched.
+fn new_day(day: u8) -> Result<(), Box<dyn Error>> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    for path in scaffold::scaffold(&root, day)? {
+        println!("wrote {}", path.display());
+    }
+    return Ok(());
 }
 
-struct Command {
-    units: i32,
-    direction: Direction,
+/// Sets the log level from the environment and from the logging flags, which
+/// may appear anywhere on the command line. Returns the remaining arguments.
+fn set_log_level(args: Vec<String>) -> Result<Vec<String>, String> {
+    log::init_from_env()?;
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verbose" => log::set_level(log::level().more_verbose()),
+            "-q" | "--quiet" => log::set_level(Level::Quiet),
+            "--log" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a value", arg))?;
+                log::set_level(value.parse()?);
+            }
+            // -v, -vv, -vvv
+            flag if flag.len() > 1
+                && flag.starts_with('-')
+                && flag[1..].chars().all(|c| c == 'v') =>
+            {
+                for _ in 1..flag.len() {
+                    log::set_level(log::level().more_verbose());
+                }
+            }
+            _ => rest.push(arg),
+        }
+    }
+    return Ok(rest);
 }
 
-#[derive(Debug)]
-struct Location {
-    horizontal: i32,
-    depth: i32,
-    aim: i32,
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+    match args.next().map(String::as_str) {
+        Some("run") => parse_run(args),
+        Some("verify") => parse_verify(args),
+        Some("bench") => parse_bench(args),
+        Some("new") => parse_new(args),
+        Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+        Some(other) => Err(format!("unknown command '{}'", other)),
+    }
 }
 
-impl Location {
-    fn process_command(mut self, command: &Command) -> Self {
-        match command.direction {
-            Direction::Forward => {
-                self.horizontal = self.horizontal + command.units;
-                self.depth = self.depth + self.aim * command.units
+fn parse_verify(mut args: std::slice::Iter<String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut answers = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_number(arg, args.next())?),
+            "--answers" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a value", arg))?;
+                answers = Some(PathBuf::from(path));
             }
-            Direction::Down => self.aim = self.aim + command.units,
-            Direction::Up => self.aim = self.aim - command.units,
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+    return Ok(Command::Verify { day, answers });
+}
+
+fn parse_new(mut args: std::slice::Iter<String>) -> Result<Command, String> {
+    let mut day = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_number(arg, args.next())?),
+            other => return Err(format!("unexpected argument '{}'", other)),
         }
-        return self;
     }
+    let day = day.ok_or("new needs --day")?;
+    return Ok(Command::New { day });
 }
 
-fn parse_command(s: String) -> Result<Command, ()> {
-    let x: Vec<_> = s.split(" ").collect();
-    let direction = match x[0] {
-        "forward" => Direction::Forward,
-        "down" => Direction::Down,
-        "up" => Direction::Up,
-        _ => return Err(()),
+fn parse_bench(mut args: std::slice::Iter<String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut runs = DEFAULT_RUNS;
+    let mut save = false;
+    let mut compare = false;
+    let mut baseline = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_number(arg, args.next())?),
+            "--part" | "-p" => part = Some(parse_number(arg, args.next())?),
+            "--runs" | "-n" => runs = parse_number(arg, args.next())?,
+            "--save" => save = true,
+            "--compare" => compare = true,
+            "--baseline" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a value", arg))?;
+                baseline = Some(PathBuf::from(path));
+            }
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+    if runs == 0 {
+        return Err("--runs must be at least 1".into());
+    }
+    let selection = match (day, part) {
+        (None, None) => Selection::All,
+        (Some(day), None) => Selection::Day(day),
+        (Some(day), Some(part)) => Selection::Part(day, part),
+        (None, Some(_)) => return Err("--part requires --day".into()),
     };
+    return Ok(Command::Bench {
+        selection,
+        runs,
+        save,
+        compare,
+        baseline,
+    });
+}
 
-    let units = x[1].parse().unwrap();
-    Ok(Command { direction, units })
+fn parse_run(mut args: std::slice::Iter<String>) -> Result<Command, String> {
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut format = Format::Text;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--format" | "-f" => {
+                format = match args.next().map(String::as_str) {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    Some(other) => {
+                        return Err(format!("unknown format '{}', expected text or json", other))
+                    }
+                    None => return Err(format!("{} needs a value", arg)),
+                }
+            }
+            "--day" | "-d" => day = Some(parse_number(arg, args.next())?),
+            "--part" | "-p" => part = Some(parse_number(arg, args.next())?),
+            "--input" | "-i" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a value", arg))?;
+                input = Some(Input::from_arg(path));
+            }
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+
+    if all && input.is_some() {
+        return Err("--input cannot be combined with --all".into());
+    }
+    let selection = match (all, day, part) {
+        (true, None, None) => Selection::All,
+        (true, _, _) => return Err("--all cannot be combined with --day or --part".into()),
+        (false, Some(day), None) => Selection::Day(day),
+        (false, Some(day), Some(part)) => Selection::Part(day, part),
+        (false, None, Some(_)) => return Err("--part requires --day".into()),
+        (false, None, None) => return Err("run needs either --day or --all".into()),
+    };
+    return Ok(Command::Run {
+        selection,
+        input,
+        format,
+    });
+}
+
+fn parse_number<T: FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got '{}'", flag, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        return line.split_whitespace().map(String::from).collect();
+    }
+
+    // the level is global, so every case runs in the one test, each starting
+    // from -q in case the environment variable is set
+    #[test]
+    fn logging_flags_set_the_level() {
+        let rest = set_log_level(args("run -q --day 1 -v")).unwrap();
+        assert_eq!(rest, args("run --day 1"));
+        assert_eq!(log::level(), Level::Info);
+
+        set_log_level(args("-q -vv")).unwrap();
+        assert_eq!(log::level(), Level::Debug);
+        set_log_level(args("-q -vvv")).unwrap();
+        assert_eq!(log::level(), Level::Trace);
+        set_log_level(args("-q -vvvv --verbose")).unwrap();
+        assert_eq!(log::level(), Level::Trace);
+        set_log_level(args("-q --verbose -v")).unwrap();
+        assert_eq!(log::level(), Level::Debug);
+
+        set_log_level(args("-q --log Trace")).unwrap();
+        assert_eq!(log::level(), Level::Trace);
+        set_log_level(args("--log info --quiet")).unwrap();
+        assert_eq!(log::level(), Level::Quiet);
+
+        assert_eq!(
+            set_log_level(args("run --log")),
+            Err("--log needs a value".to_string())
+        );
+        assert!(set_log_level(args("--log loud")).is_err());
+        log::set_level(Level::Quiet);
+    }
+
+    #[test]
+    fn run_selects_a_day_or_part() {
+        assert_eq!(
+            parse_args(&args("run --day 3")),
+            Ok(Command::Run {
+                selection: Selection::Day(3),
+                input: None,
+                format: Format::Text,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run -d 3 -p 2 -f json")),
+            Ok(Command::Run {
+                selection: Selection::Part(3, 2),
+                input: None,
+                format: Format::Json,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run --all")),
+            Ok(Command::Run {
+                selection: Selection::All,
+                input: None,
+                format: Format::Text,
+            })
+        );
+    }
+
+    #[test]
+    fn run_rejects_conflicting_flags() {
+        let err = |line| parse_args(&args(line)).unwrap_err();
+        assert_eq!(
+            err("run --all --day 1"),
+            "--all cannot be combined with --day or --part"
+        );
+        assert_eq!(
+            err("run --all --part 1"),
+            "--all cannot be combined with --day or --part"
+        );
+        assert_eq!(err("run --part 1"), "--part requires --day");
+        assert_eq!(
+            err("run --all --input input1.t"),
+            "--input cannot be combined with --all"
+        );
+        assert_eq!(err("run"), "run needs either --day or --all");
+        assert_eq!(err("run --day one"), "--day expects a number, got 'one'");
+        assert_eq!(err("run --day"), "--day needs a value");
+    }
+
+    #[test]
+    fn bench_selects_a_day_or_part() {
+        assert_eq!(
+            parse_args(&args("bench --day 5 --part 1 --runs 3 --save")),
+            Ok(Command::Bench {
+                selection: Selection::Part(5, 1),
+                runs: 3,
+                save: true,
+                compare: false,
+                baseline: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("bench --part 1")),
+            Err("--part requires --day".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("bench --runs 0")),
+            Err("--runs must be at least 1".to_string())
+        );
+    }
+
+    #[test]
+    fn other_commands() {
+        assert_eq!(parse_args(&[]), Ok(Command::Help));
+        assert_eq!(
+            parse_args(&args("verify -d 2")),
+            Ok(Command::Verify {
+                day: Some(2),
+                answers: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("new --day 25")),
+            Ok(Command::New { day: 25 })
+        );
+        assert_eq!(parse_args(&args("new")), Err("new needs --day".to_string()));
+        assert_eq!(
+            parse_args(&args("solve")),
+            Err("unknown command 'solve'".to_string())
+        );
+    }
 }
@@ -0,0 +1,107 @@
+//! Reading the capital letters that some puzzles draw in dots, in the 4 wide
+//! and 6 tall font Advent of Code uses, with a blank column between letters.
+
+use std::collections::HashSet;
+
+pub const GLYPH_WIDTH: i32 = 4;
+pub const GLYPH_HEIGHT: i32 = 6;
+
+/// Stands in for a glyph that is not in the font.
+pub const UNKNOWN: char = '?';
+
+const FONT: &[(char, [&str; GLYPH_HEIGHT as usize])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Reads the letters drawn by `dots`, left to right, where each dot is an
+/// `(x, y)` position and the first letter's top left corner is at `(0, 0)`.
+/// Glyphs that are not in the font read as [`UNKNOWN`].
+pub fn recognize(dots: &HashSet<(i32, i32)>) -> String {
+    let max_x = match dots.iter().map(|(x, _)| *x).max() {
+        Some(max_x) => max_x,
+        None => return String::new(),
+    };
+    let num_letters = max_x / (GLYPH_WIDTH + 1) + 1;
+
+    return (0..num_letters)
+        .map(|idx| {
+            let left = idx * (GLYPH_WIDTH + 1);
+            let glyph: Vec<String> = (0..GLYPH_HEIGHT)
+                .map(|y| {
+                    (0..GLYPH_WIDTH)
+                        .map(|x| {
+                            if dots.contains(&(left + x, y)) {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            FONT.iter()
+                .find(|(_, rows)| rows.iter().zip(&glyph).all(|(a, b)| a == b))
+                .map(|(letter, _)| *letter)
+                .unwrap_or(UNKNOWN)
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dots(art: &str) -> HashSet<(i32, i32)> {
+        let mut dots = HashSet::new();
+        for (y, row) in art.lines().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c == '#' {
+                    dots.insert((x as i32, y as i32));
+                }
+            }
+        }
+        return dots;
+    }
+
+    #[test]
+    fn reads_every_letter_of_the_font() {
+        for (letter, rows) in FONT {
+            assert_eq!(recognize(&dots(&rows.join("\n"))), letter.to_string());
+        }
+    }
+
+    #[test]
+    fn reads_letters_side_by_side() {
+        let art = "\
+.##..#..#..##.
+#..#.#..#.#..#
+#..#.####.#...
+####.#..#.#.##
+#..#.#..#.#..#
+#..#.#..#..###";
+        assert_eq!(recognize(&dots(art)), "AHG");
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        let square = "#####\n#...#\n#...#\n#...#\n#####";
+        assert_eq!(recognize(&dots(square)), "?");
+        assert_eq!(recognize(&HashSet::new()), "");
+    }
+}
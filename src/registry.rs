@@ -0,0 +1,176 @@
+//! The table of solved days, and lookups of a day or part in it.
+
+use std::fmt::Display;
+
+use crate::day1::Day1;
+use crate::day10::Day10;
+use crate::day11::Day11;
+use crate::day12::Day12;
+use crate::day13::Day13;
+use crate::day14::Day14;
+use crate::day15::Day15;
+use crate::day16::Day16;
+use crate::day17::Day17;
+use crate::day18::Day18;
+use crate::day19::Day19;
+use crate::day2::Day2;
+use crate::day20::Day20;
+use crate::day21::Day21;
+use crate::day22::Day22;
+use crate::day23::Day23;
+use crate::day24::Day24;
+use crate::day25::Day25;
+use crate::day3::Day3;
+use crate::day4::Day4;
+use crate::day5::Day5;
+use crate::day6::Day6;
+use crate::day7::Day7;
+use crate::day8::Day8;
+use crate::day9::Day9;
+use crate::solution::Solver;
+
+pub const LAST_DAY: u8 = 25;
+pub const LAST_PART: u8 = 2;
+
+/// Every day that has a solution so far, in calendar order.
+const DAYS: &[&dyn Solver] = &[
+    &Day1, &Day2, &Day3, &Day4, &Day5, &Day6, &Day7, &Day8, &Day9, &Day10, &Day11, &Day12, &Day13,
+    &Day14, &Day15, &Day16, &Day17, &Day18, &Day19, &Day20, &Day21, &Day22, &Day23, &Day24, &Day25,
+];
+
+/// A day together with the parts of it that should be run.
+pub struct Job {
+    pub solver: &'static dyn Solver,
+    pub parts: Vec<u8>,
+}
+
+impl Job {
+    pub fn day(&self) -> u8 {
+        self.solver.day()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LookupError {
+    /// The day is outside of the advent calendar.
+    NoSuchDay(u8),
+    /// The part is neither 1 nor 2.
+    NoSuchPart(u8),
+    /// The day exists in the calendar but has no solver yet.
+    DayNotSolved(u8),
+    /// The day has solvers, but not for this part.
+    PartNotSolved { day: u8, part: u8 },
+}
+
+impl Display for LookupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LookupError::NoSuchDay(day) => {
+                write!(
+                    f,
+                    "there is no day {}, days run from 1 to {}",
+                    day, LAST_DAY
+                )
+            }
+            LookupError::NoSuchPart(part) => {
+                write!(f, "there is no part {}, parts are 1 or {}", part, LAST_PART)
+            }
+            LookupError::DayNotSolved(day) => write!(f, "day {} has not been solved yet", day),
+            LookupError::PartNotSolved { day, part } => {
+                write!(f, "day {} part {} has not been solved yet", day, part)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LookupError {}
+
+/// Every solved part of every day.
+pub fn all() -> Vec<Job> {
+    DAYS.iter()
+        .map(|&solver| Job {
+            solver,
+            parts: solver.parts(),
+        })
+        .collect()
+}
+
+/// Every solved part of a single day.
+pub fn for_day(day: u8) -> Result<Job, LookupError> {
+    return day_in(DAYS, day);
+}
+
+/// Every solved part of a single day out of `days`.
+fn day_in(days: &[&'static dyn Solver], day: u8) -> Result<Job, LookupError> {
+    if day == 0 || day > LAST_DAY {
+        return Err(LookupError::NoSuchDay(day));
+    }
+    let solver = *days
+        .iter()
+        .find(|s| s.day() == day)
+        .ok_or(LookupError::DayNotSolved(day))?;
+    // a freshly scaffolded day is registered before either part is solved
+    let parts = solver.parts();
+    if parts.is_empty() {
+        return Err(LookupError::DayNotSolved(day));
+    }
+    return Ok(Job { solver, parts });
+}
+
+/// A single part of a day.
+pub fn find(day: u8, part: u8) -> Result<Job, LookupError> {
+    if part == 0 || part > LAST_PART {
+        return Err(LookupError::NoSuchPart(part));
+    }
+    let job = for_day(day)?;
+    if !job.parts.contains(&part) {
+        return Err(LookupError::PartNotSolved { day, part });
+    }
+    return Ok(Job {
+        solver: job.solver,
+        parts: vec![part],
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+    use crate::solution::{Solution, Unsolved};
+
+    struct Scaffolded;
+
+    impl Solution for Scaffolded {
+        const DAY: u8 = 25;
+
+        type Parsed = ();
+        type Part1 = Unsolved;
+        type Part2 = Unsolved;
+
+        fn parse(_input: &str) -> Result<(), ParseError> {
+            return Ok(());
+        }
+
+        fn part1(_parsed: &()) -> Unsolved {
+            return Unsolved;
+        }
+
+        fn part2(_parsed: &()) -> Unsolved {
+            return Unsolved;
+        }
+    }
+
+    #[test]
+    fn days_without_solved_parts_are_not_solved() {
+        let err = day_in(&[&Scaffolded], 25).err().unwrap();
+        assert_eq!(err, LookupError::DayNotSolved(25));
+    }
+
+    #[test]
+    fn looks_up_days_and_parts() {
+        assert_eq!(for_day(1).unwrap().parts, [1, 2]);
+        assert_eq!(find(3, 2).unwrap().parts, [2]);
+        assert_eq!(for_day(0).err().unwrap(), LookupError::NoSuchDay(0));
+        assert_eq!(find(1, 3).err().unwrap(), LookupError::NoSuchPart(3));
+    }
+}
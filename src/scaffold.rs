@@ -0,0 +1,318 @@
+//! Generating the files for a new day: its module, with a placeholder example
+//! to test against, an empty input file, and its entries in `lib.rs` and the
+//! registry.
+
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::input;
+use crate::registry::LAST_DAY;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day is outside of the advent calendar.
+    NoSuchDay(u8),
+    /// The day already has a module.
+    Exists(PathBuf),
+    /// A file to register the day in does not look the way it should.
+    Unrecognized {
+        path: PathBuf,
+        reason: String,
+    },
+    Io {
+        path: PathBuf,
+        err: io::Error,
+    },
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::NoSuchDay(day) => {
+                write!(
+                    f,
+                    "there is no day {}, days run from 1 to {}",
+                    day, LAST_DAY
+                )
+            }
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Unrecognized { path, reason } => {
+                write!(f, "{}: {}", path.display(), reason)
+            }
+            ScaffoldError::Io { path, err } => write!(f, "{}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// The source of a new day's module: a `Solution` that parses the input into
+/// its lines, with both parts `Unsolved` so the runner leaves them out.
+pub fn module_source(day: u8) -> String {
+    return format!(
+        r#"//! Day {day}
+
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::solution::{{Solution, Unsolved}};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    const DAY: u8 = {day};
+
+    type Parsed = Vec<String>;
+    type Part1 = Unsolved;
+    type Part2 = Unsolved;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {{
+        Ok(lines(input).map(|line| line.text.to_owned()).collect())
+    }}
+
+    fn part1(_: &Vec<String>) -> Unsolved {{
+        Unsolved
+    }}
+
+    fn part2(_: &Vec<String>) -> Unsolved {{
+        Unsolved
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    // the worked example from the puzzle
+    const EXAMPLE: &str = "";
+
+    #[test]
+    fn parses_example() {{
+        assert!(Day{day}::parse(EXAMPLE).is_ok());
+    }}
+}}
+"#,
+        day = day
+    );
+}
+
+/// The `lib.rs` source with `pub mod dayN;` added among the other modules,
+/// which are kept in sorted order.
+pub fn add_module(lib: &str, day: u8) -> Result<String, String> {
+    let line = format!("pub mod day{};", day);
+    let modules: Vec<usize> = line_indices(lib, |text| text.starts_with("pub mod "));
+    if modules.is_empty() {
+        return Err("expected a list of 'pub mod' lines".into());
+    }
+    let mut lines: Vec<&str> = lib.lines().collect();
+    if lines.contains(&line.as_str()) {
+        return Err(format!("'{}' is already there", line));
+    }
+    let at = modules
+        .iter()
+        .copied()
+        .find(|&idx| module_name(lines[idx], "pub mod ") > module_name(&line, "pub mod "))
+        .unwrap_or(modules[modules.len() - 1] + 1);
+    lines.insert(at, &line);
+    return Ok(join_lines(&lines, lib));
+}
+
+/// The registry source with `DayN` imported among the other days, which are
+/// kept in sorted order, and added to the table of days in calendar order.
+pub fn add_solver(registry: &str, day: u8) -> Result<String, String> {
+    let import = format!("use crate::day{day}::Day{day};", day = day);
+    let imports = line_indices(registry, |text| text.starts_with("use crate::day"));
+    if imports.is_empty() {
+        return Err("expected the days to be imported with 'use crate::dayN::DayN;'".into());
+    }
+    let mut lines: Vec<&str> = registry.lines().collect();
+    if lines.contains(&import.as_str()) {
+        return Err(format!("'{}' is already there", import));
+    }
+
+    const TABLE: &str = "const DAYS: &[&dyn Solver] = &[";
+    let start = lines
+        .iter()
+        .position(|text| text.starts_with(TABLE))
+        .ok_or_else(|| format!("expected the table of days, '{}'", TABLE))?;
+    let end = (start..lines.len())
+        .find(|&idx| lines[idx].trim_end().ends_with("];"))
+        .ok_or("expected the table of days to end with '];'")?;
+    let entries: String = lines[start..=end].join(" ");
+    let entries = &entries[TABLE.len()..entries.len() - 2];
+    let mut days = Vec::new();
+    for entry in entries.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let number = entry
+            .strip_prefix("&Day")
+            .and_then(|n| n.parse::<u8>().ok())
+            .ok_or_else(|| format!("unexpected entry '{}' in the table of days", entry))?;
+        days.push(number);
+    }
+    days.push(day);
+    days.sort_unstable();
+    let table = table_lines(TABLE, &days);
+
+    // replace the table first, as it comes after the imports
+    lines.splice(start..=end, table.iter().map(String::as_str));
+    let at = imports
+        .iter()
+        .copied()
+        .find(|&idx| module_name(lines[idx], "use crate::") > module_name(&import, "use crate::"))
+        .unwrap_or(imports[imports.len() - 1] + 1);
+    lines.insert(at, &import);
+    return Ok(join_lines(&lines, registry));
+}
+
+/// The table of days, laid out the way rustfmt would: as many entries to a
+/// line as fit in 100 columns.
+fn table_lines(opening: &str, days: &[u8]) -> Vec<String> {
+    const WIDTH: usize = 100;
+    let mut table = vec![opening.to_owned()];
+    let mut current = String::from("   ");
+    for day in days {
+        let entry = format!(" &Day{},", day);
+        if current.len() + entry.len() > WIDTH {
+            table.push(current);
+            current = String::from("   ");
+        }
+        current += &entry;
+    }
+    table.push(current);
+    table.push("];".to_owned());
+    return table;
+}
+
+/// The module a `pub mod` or `use` line names, which is what rustfmt sorts
+/// them by (so `day1` comes before `day10`).
+fn module_name<'a>(line: &'a str, prefix: &str) -> &'a str {
+    let rest = line.strip_prefix(prefix).unwrap_or(line);
+    return rest.split([':', ';']).next().unwrap_or(rest);
+}
+
+fn line_indices(text: &str, matches: impl Fn(&str) -> bool) -> Vec<usize> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| matches(line))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+fn join_lines(lines: &[&str], original: &str) -> String {
+    let mut text = lines.join("\n");
+    if original.ends_with('\n') {
+        text.push('\n');
+    }
+    return text;
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|err| ScaffoldError::Io {
+        path: path.to_owned(),
+        err,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|err| ScaffoldError::Io {
+        path: path.to_owned(),
+        err,
+    })
+}
+
+/// Creates day `day` in the crate at `root`, returning every file created or
+/// changed. Nothing is written unless the day can be registered, and an
+/// existing input file is left alone.
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if day == 0 || day > LAST_DAY {
+        return Err(ScaffoldError::NoSuchDay(day));
+    }
+    let module = root.join("src").join(format!("day{}.rs", day));
+    if module.exists() {
+        return Err(ScaffoldError::Exists(module));
+    }
+
+    let lib = root.join("src").join("lib.rs");
+    let registry = root.join("src").join("registry.rs");
+    let unrecognized = |path: &Path| {
+        let path = path.to_owned();
+        move |reason| ScaffoldError::Unrecognized { path, reason }
+    };
+    let new_lib = add_module(&read(&lib)?, day).map_err(unrecognized(&lib))?;
+    let new_registry = add_solver(&read(&registry)?, day).map_err(unrecognized(&registry))?;
+
+    let mut changed = vec![module.clone()];
+    write(&module, &module_source(day))?;
+    let input = root.join(input::default_name(day));
+    if !input.exists() {
+        write(&input, "")?;
+        changed.push(input);
+    }
+    write(&lib, &new_lib)?;
+    write(&registry, &new_registry)?;
+    changed.extend([lib, registry]);
+    return Ok(changed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "//! Docs.
+
+pub mod answers;
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod error;
+";
+
+    const REGISTRY: &str = "use std::fmt::Display;
+
+use crate::day1::Day1;
+use crate::day10::Day10;
+use crate::day2::Day2;
+use crate::solution::Solver;
+
+const DAYS: &[&dyn Solver] = &[
+    &Day1, &Day2, &Day10,
+];
+";
+
+    #[test]
+    fn adds_module_in_order() {
+        let lib = add_module(LIB, 3).unwrap();
+        assert!(lib.contains("pub mod day2;\npub mod day3;\npub mod error;\n"));
+        let lib = add_module(LIB, 11).unwrap();
+        assert!(lib.contains("pub mod day10;\npub mod day11;\npub mod day2;\n"));
+        assert!(add_module(LIB, 1).unwrap_err().contains("already"));
+    }
+
+    #[test]
+    fn adds_solver_in_order() {
+        let registry = add_solver(REGISTRY, 3).unwrap();
+        assert!(registry
+            .contains("use crate::day2::Day2;\nuse crate::day3::Day3;\nuse crate::solution"));
+        assert!(registry.contains("&[\n    &Day1, &Day2, &Day3, &Day10,\n];\n"));
+        let registry = add_solver(REGISTRY, 11).unwrap();
+        assert!(registry.contains("use crate::day10::Day10;\nuse crate::day11::Day11;\n"));
+        assert!(add_solver(REGISTRY, 10).is_err());
+    }
+
+    #[test]
+    fn wraps_long_tables() {
+        let days: Vec<u8> = (1..=25).collect();
+        let table = table_lines("const DAYS: &[&dyn Solver] = &[", &days);
+        assert_eq!(table.len(), 4);
+        assert!(table[1].ends_with("&Day13,"));
+        assert!(table.iter().all(|line| line.len() <= 100));
+    }
+
+    #[test]
+    fn generated_module_names_the_day() {
+        let source = module_source(7);
+        assert!(source.contains("pub struct Day7;"));
+        assert!(source.contains("const DAY: u8 = 7;"));
+    }
+}
@@ -0,0 +1,310 @@
+//! The interface shared by every day.
+
+use std::{
+    any::TypeId,
+    fmt::Debug,
+    time::{Duration, Instant},
+};
+
+use crate::bigint::BigUint;
+use crate::error::{Error, ParseError};
+use crate::input::Input;
+use crate::json;
+
+/// A day's puzzle. The input is parsed once into a typed value (a `HeightMap`,
+/// a `BingoGame`, ...) and both parts are then solved from that same value.
+pub trait Solution {
+    const DAY: u8;
+
+    type Parsed;
+    type Part1: AnswerValue + Debug;
+    type Part2: AnswerValue + Debug;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(parsed: &Self::Parsed) -> Self::Part1;
+    fn part2(parsed: &Self::Parsed) -> Self::Part2;
+}
+
+/// Answer type for a part that has not been solved yet. Parts answering
+/// `Unsolved` are left out of the registry instead of being run.
+#[derive(Debug)]
+pub struct Unsolved;
+
+fn is_solved<T: 'static>() -> bool {
+    TypeId::of::<T>() != TypeId::of::<Unsolved>()
+}
+
+/// What kind of value an answer is. The names are part of the schema of the
+/// runner's JSON output, so they stay the same whatever type a day answers
+/// with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A whole number, of any size, written in decimal.
+    Integer,
+    /// Free text, such as letters read off a display.
+    Text,
+    /// Several values, written as `(a, b)`.
+    Tuple,
+    /// No answer: the part is unsolved, or the input has no answer.
+    None,
+}
+
+impl Kind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Integer => "integer",
+            Kind::Text => "text",
+            Kind::Tuple => "tuple",
+            Kind::None => "none",
+        }
+    }
+}
+
+/// A value a part can answer with.
+pub trait AnswerValue: 'static {
+    fn kind(&self) -> Kind;
+
+    /// The answer the way `run` prints it and `answers.txt` records it.
+    fn format(&self) -> String;
+}
+
+macro_rules! integer_answers {
+    ($($t:ty),*) => {
+        $(
+            impl AnswerValue for $t {
+                fn kind(&self) -> Kind {
+                    Kind::Integer
+                }
+
+                fn format(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+integer_answers!(i32, i64, isize, u32, u64, usize, BigUint);
+
+impl AnswerValue for String {
+    fn kind(&self) -> Kind {
+        Kind::Text
+    }
+
+    fn format(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<A: AnswerValue, B: AnswerValue> AnswerValue for (A, B) {
+    fn kind(&self) -> Kind {
+        Kind::Tuple
+    }
+
+    fn format(&self) -> String {
+        format!("({}, {})", self.0.format(), self.1.format())
+    }
+}
+
+/// Why a part has no answer for an input that parsed, such as a burrow whose
+/// amphipods can't all be sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoAnswer(pub &'static str);
+
+/// Answers are written as they are, and missing ones with the reason.
+impl<T: AnswerValue> AnswerValue for Result<T, NoAnswer> {
+    fn kind(&self) -> Kind {
+        self.as_ref().map_or(Kind::None, T::kind)
+    }
+
+    fn format(&self) -> String {
+        match self {
+            Ok(answer) => answer.format(),
+            Err(NoAnswer(reason)) => format!("no answer: {}", reason),
+        }
+    }
+}
+
+impl AnswerValue for Unsolved {
+    fn kind(&self) -> Kind {
+        Kind::None
+    }
+
+    fn format(&self) -> String {
+        "unsolved".to_owned()
+    }
+}
+
+/// The answer to one part, formatted for display, and how long solving it took
+/// (not counting reading and parsing the input).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: u8,
+    pub value: String,
+    pub kind: Kind,
+    pub elapsed: Duration,
+}
+
+impl Answer {
+    /// The answer as a JSON record, as printed by `run --format json`. The
+    /// fields are, in order: `day` and `part`, the `answer` as text, its
+    /// `type` (the name of its [`Kind`]), the `input` it was solved from,
+    /// and the `parse_ns` and `solve_ns` it took.
+    pub fn record(&self, day: u8, input: &str, parse_time: Duration) -> json::Object {
+        json::Object::new()
+            .number("day", day)
+            .number("part", self.part)
+            .string("answer", &self.value)
+            .string("type", self.kind.name())
+            .string("input", input)
+            .number("parse_ns", parse_time.as_nanos())
+            .number("solve_ns", self.elapsed.as_nanos())
+    }
+}
+
+/// Object safe view of a `Solution`, so that the runner can hold every day in
+/// one table regardless of their parsed and answer types.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    /// The parts of the day that have a solution, in order.
+    fn parts(&self) -> Vec<u8>;
+
+    /// Reads and parses `input` once, then solves each of `parts` from it.
+    fn run(&self, input: &Input, parts: &[u8]) -> Result<Vec<Answer>, Error>;
+
+    /// Parses `text` and solves each of `parts` from it, also returning how long
+    /// parsing took.
+    fn solve(&self, text: &str, parts: &[u8]) -> Result<(Duration, Vec<Answer>), ParseError>;
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parts(&self) -> Vec<u8> {
+        let mut parts = Vec::new();
+        if is_solved::<S::Part1>() {
+            parts.push(1);
+        }
+        if is_solved::<S::Part2>() {
+            parts.push(2);
+        }
+        return parts;
+    }
+
+    fn run(&self, input: &Input, parts: &[u8]) -> Result<Vec<Answer>, Error> {
+        let text = input.read()?;
+        let (_, answers) = self.solve(&text, parts)?;
+        return Ok(answers);
+    }
+
+    fn solve(&self, text: &str, parts: &[u8]) -> Result<(Duration, Vec<Answer>), ParseError> {
+        let start = Instant::now();
+        let parsed = S::parse(text).map_err(|err| err.for_day(S::DAY))?;
+        let parse_time = start.elapsed();
+        let answers = parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let (value, kind) = match part {
+                    1 => {
+                        let answer = S::part1(&parsed);
+                        (answer.format(), answer.kind())
+                    }
+                    _ => {
+                        let answer = S::part2(&parsed);
+                        (answer.format(), answer.kind())
+                    }
+                };
+                Answer {
+                    part,
+                    value,
+                    kind,
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect();
+        return Ok((parse_time, answers));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Example;
+
+    impl Solution for Example {
+        const DAY: u8 = 1;
+
+        type Parsed = u64;
+        type Part1 = (BigUint, usize);
+        type Part2 = Result<String, NoAnswer>;
+
+        fn parse(input: &str) -> Result<u64, ParseError> {
+            input
+                .trim()
+                .parse()
+                .map_err(|_| ParseError::new(1, "expected a number"))
+        }
+
+        fn part1(n: &u64) -> (BigUint, usize) {
+            (BigUint::from(*n), 2)
+        }
+
+        fn part2(n: &u64) -> Result<String, NoAnswer> {
+            if *n == 0 {
+                return Err(NoAnswer("nothing to spell"));
+            }
+            Ok("ABC".to_owned())
+        }
+    }
+
+    #[test]
+    fn formats_answers_by_kind() {
+        assert_eq!(
+            (42usize.kind(), 42usize.format()),
+            (Kind::Integer, "42".into())
+        );
+        assert_eq!((-3i64).format(), "-3");
+        let text = "#.\n.#".to_owned();
+        assert_eq!((text.kind(), text.format()), (Kind::Text, text.clone()));
+        assert_eq!((1337usize, 2758usize).format(), "(1337, 2758)");
+        assert_eq!(Err::<u64, _>(NoAnswer("why")).kind(), Kind::None);
+        assert_eq!(Err::<u64, _>(NoAnswer("why")).format(), "no answer: why");
+        assert_eq!(Ok::<_, NoAnswer>(7u64).format(), "7");
+    }
+
+    #[test]
+    fn json_records_follow_the_schema() {
+        let (_, answers) = Example.solve("5", &[1, 2]).unwrap();
+        let records: Vec<String> = answers
+            .iter()
+            .map(|answer| {
+                let answer = Answer {
+                    elapsed: Duration::from_nanos(20),
+                    ..answer.clone()
+                };
+                answer
+                    .record(1, "input1.t", Duration::from_nanos(10))
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                "{\"day\":1,\"part\":1,\"answer\":\"(5, 2)\",\"type\":\"tuple\",\
+                 \"input\":\"input1.t\",\"parse_ns\":10,\"solve_ns\":20}",
+                "{\"day\":1,\"part\":2,\"answer\":\"ABC\",\"type\":\"text\",\
+                 \"input\":\"input1.t\",\"parse_ns\":10,\"solve_ns\":20}",
+            ]
+        );
+        let (_, answers) = Example.solve("0", &[2]).unwrap();
+        assert_eq!(
+            (answers[0].kind, answers[0].value.as_str()),
+            (Kind::None, "no answer: nothing to spell")
+        );
+    }
+}